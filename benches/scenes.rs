@@ -0,0 +1,115 @@
+//! Benchmarks the world update of all demo scenes and of scalable pile scenes.<br>
+//! Usage: `cargo bench --bench scenes -- [filter] [--ticks <n>] [--warmup <n>]`<br>
+//! All spawners run until they are done before measuring, so every tick simulates the full body count of the scene.
+
+use std::{env, process, time::Instant};
+
+use rustycs::environment::force::{Force, GRAVITY_EARTH};
+
+use rustycs_macroquad_demo::*;
+
+const TICK_RATE: f32 = 256.;
+const FORCE: Force = GRAVITY_EARTH;
+const PILE_SIZES: [usize; 3] = [100, 1_000, 5_000];
+/// upper bound of simulation time spent on spawning before measuring
+const MAX_SPAWN_SECONDS: f32 = 60.;
+
+fn main() {
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    let ticks: usize = take_flag(&mut args, "--ticks").unwrap_or(600);
+    let warmup: usize = take_flag(&mut args, "--warmup").unwrap_or(60);
+
+    // cargo passes `--bench` to custom harnesses
+    let filter = args.iter().find(|arg| !arg.starts_with("--"));
+
+    let factory = WorldFactory::new(TICK_RATE, FORCE);
+
+    let scenes = WorldFactory::DEMO_SCENES
+        .iter()
+        .map(|name| name.to_string())
+        .chain(
+            PILE_SIZES
+                .iter()
+                .map(|n| format!("{}{n}", WorldFactory::PILE_SCENE_PREFIX)),
+        )
+        .filter(|name| filter.is_none_or(|f| name.contains(f.as_str())));
+
+    println!(
+        "{:<32} {:>7} {:>9} {:>9} {:>9} {:>9} {:>9}",
+        "scene [ms per tick]", "bodies", "mean", "median", "p95", "p99", "max"
+    );
+
+    for name in scenes {
+        let Some(mut scene) = factory.demo_by_name(&name) else {
+            continue;
+        };
+
+        let (mut world, _, mut spawners, _, _) = scene.extract();
+        world.set_collision_precision(25);
+
+        // spawners run on their schedule, so bodies enter the scene one burst at a time as in the demo
+        let mut nr_of_updates: u32 = 0;
+
+        while spawners.iter().any(has_pending_bodies)
+            && nr_of_updates as f32 * world.get_delta_time() < MAX_SPAWN_SECONDS
+        {
+            world.update();
+            nr_of_updates += 1;
+
+            let now = nr_of_updates as f32 * world.get_delta_time();
+
+            for spawner in &mut spawners {
+                world.add_bodies(spawner.spawn_due(now));
+            }
+        }
+
+        for _ in 0..warmup {
+            world.update();
+        }
+
+        let mut durations = RollingStats::new(ticks);
+
+        for _ in 0..ticks {
+            let update_start = Instant::now();
+            world.update();
+            durations.push(update_start.elapsed().as_secs_f32());
+        }
+
+        let Some(s) = durations.summary() else {
+            continue;
+        };
+
+        println!(
+            "{name:<32} {:>7} {:>9.3} {:>9.3} {:>9.3} {:>9.3} {:>9.3}",
+            world.get_bodies().len(),
+            s.mean * 1000.,
+            s.median * 1000.,
+            s.p95 * 1000.,
+            s.p99 * 1000.,
+            s.max * 1000.
+        );
+    }
+}
+
+/// Unlimited spawners never finish, so they are not waited for.
+fn has_pending_bodies(spawner: &BodySpawner) -> bool {
+    !spawner.paused && spawner.has_templates() && spawner.remaining().is_some_and(|n| n > 0)
+}
+
+/// Removes `<flag> <value>` from the arguments and returns the parsed value.
+fn take_flag<T: std::str::FromStr>(args: &mut Vec<String>, flag: &str) -> Option<T> {
+    let idx = args.iter().position(|arg| arg == flag)?;
+    args.remove(idx);
+
+    if idx >= args.len() {
+        eprintln!("missing value after {flag}");
+        process::exit(1);
+    }
+
+    let value = args.remove(idx);
+
+    Some(value.parse().unwrap_or_else(|_| {
+        eprintln!("invalid value '{value}' for {flag}");
+        process::exit(1);
+    }))
+}
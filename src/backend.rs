@@ -0,0 +1,253 @@
+//! Drawing backends the renderer targets instead of calling macroquad directly.<br>
+//! - `MacroquadBackend` draws onto the window, batching untextured primitives into meshes.
+//! - `RecordingBackend` only records the draw commands, e.g. to inspect the rendering logic in tests.
+
+use macroquad::{
+    color::{Color, WHITE},
+    input::mouse_position,
+    math::{vec2, Vec2},
+    models::{draw_mesh, Mesh, Vertex},
+    text::draw_text,
+    texture::Texture2D,
+    window::{clear_background, screen_height, screen_width},
+};
+
+use crate::batch::MeshBatch;
+
+pub trait DrawBackend {
+    fn screen_size(&self) -> (f32, f32);
+
+    fn mouse_position(&self) -> (f32, f32);
+
+    fn clear(&mut self, color: Color);
+
+    fn line(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, thickness: f32, color: Color);
+
+    fn circle(&mut self, x: f32, y: f32, r: f32, color: Color);
+
+    fn circle_lines(&mut self, x: f32, y: f32, r: f32, thickness: f32, color: Color);
+
+    fn triangle(&mut self, a: Vec2, b: Vec2, c: Vec2, color: Color);
+
+    fn rectangle(&mut self, x: f32, y: f32, w: f32, h: f32, color: Color) {
+        self.triangle(vec2(x, y), vec2(x + w, y), vec2(x + w, y + h), color);
+        self.triangle(vec2(x, y), vec2(x + w, y + h), vec2(x, y + h), color);
+    }
+
+    fn text(&mut self, text: &str, x: f32, y: f32, font_size: f32, color: Color);
+
+    /// Polygon in screen coordinates with one UV coordinate per point, split into the given triangles.<br>
+    /// Backends without texture support draw nothing.
+    fn textured_polygon(
+        &mut self,
+        _texture: &Texture2D,
+        _points: &[Vec2],
+        _uvs: &[Vec2],
+        _triangles: &[[usize; 3]],
+    ) {
+    }
+
+    /// Submits all pending primitives, has to be called at the end of each frame.
+    fn flush(&mut self) {}
+}
+
+// ---------------------- MACROQUAD ----------------------
+#[derive(Default)]
+pub struct MacroquadBackend {
+    batch: MeshBatch,
+}
+
+impl MacroquadBackend {
+    pub fn new() -> MacroquadBackend {
+        Self::default()
+    }
+}
+
+impl DrawBackend for MacroquadBackend {
+    fn screen_size(&self) -> (f32, f32) {
+        (screen_width(), screen_height())
+    }
+
+    fn mouse_position(&self) -> (f32, f32) {
+        mouse_position()
+    }
+
+    fn clear(&mut self, color: Color) {
+        self.batch.flush();
+        clear_background(color);
+    }
+
+    fn line(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, thickness: f32, color: Color) {
+        self.batch.push_line(x1, y1, x2, y2, thickness, color);
+    }
+
+    fn circle(&mut self, x: f32, y: f32, r: f32, color: Color) {
+        self.batch.push_circle(x, y, r, color);
+    }
+
+    fn circle_lines(&mut self, x: f32, y: f32, r: f32, thickness: f32, color: Color) {
+        self.batch.push_circle_lines(x, y, r, thickness, color);
+    }
+
+    fn triangle(&mut self, a: Vec2, b: Vec2, c: Vec2, color: Color) {
+        self.batch.push_triangle(a, b, c, color);
+    }
+
+    fn text(&mut self, text: &str, x: f32, y: f32, font_size: f32, color: Color) {
+        // text is drawn immediately, so everything batched before has to be drawn first
+        self.batch.flush();
+        draw_text(text, x, y, font_size, color);
+    }
+
+    fn textured_polygon(
+        &mut self,
+        texture: &Texture2D,
+        points: &[Vec2],
+        uvs: &[Vec2],
+        triangles: &[[usize; 3]],
+    ) {
+        self.batch.flush();
+
+        draw_mesh(&Mesh {
+            vertices: points
+                .iter()
+                .zip(uvs)
+                .map(|(p, uv)| Vertex::new(p.x, p.y, 0., uv.x, uv.y, WHITE))
+                .collect(),
+            indices: triangles.iter().flatten().map(|idx| *idx as u16).collect(),
+            texture: Some(texture.clone()),
+        });
+    }
+
+    fn flush(&mut self) {
+        self.batch.flush();
+    }
+}
+
+// ---------------------- RECORDING ----------------------
+#[derive(Clone, Debug, PartialEq)]
+pub enum DrawCommand {
+    Clear(Color),
+    Line {
+        from: Vec2,
+        to: Vec2,
+        thickness: f32,
+        color: Color,
+    },
+    Circle {
+        center: Vec2,
+        r: f32,
+        color: Color,
+    },
+    CircleLines {
+        center: Vec2,
+        r: f32,
+        thickness: f32,
+        color: Color,
+    },
+    Triangle {
+        points: [Vec2; 3],
+        color: Color,
+    },
+    Text {
+        text: String,
+        position: Vec2,
+        font_size: f32,
+        color: Color,
+    },
+    TexturedPolygon {
+        points: Vec<Vec2>,
+    },
+}
+
+/// Headless backend with a fixed screen size and mouse position, that records all draw commands in order.
+pub struct RecordingBackend {
+    pub screen: (f32, f32),
+    pub mouse: (f32, f32),
+    pub commands: Vec<DrawCommand>,
+}
+
+impl RecordingBackend {
+    pub fn new(width: f32, height: f32) -> RecordingBackend {
+        RecordingBackend {
+            screen: (width, height),
+            mouse: (0., 0.),
+            commands: Vec::new(),
+        }
+    }
+
+    pub fn texts(&self) -> impl Iterator<Item = &str> {
+        self.commands.iter().filter_map(|command| match command {
+            DrawCommand::Text { text, .. } => Some(text.as_str()),
+            _ => None,
+        })
+    }
+}
+
+impl DrawBackend for RecordingBackend {
+    fn screen_size(&self) -> (f32, f32) {
+        self.screen
+    }
+
+    fn mouse_position(&self) -> (f32, f32) {
+        self.mouse
+    }
+
+    fn clear(&mut self, color: Color) {
+        self.commands.push(DrawCommand::Clear(color));
+    }
+
+    fn line(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, thickness: f32, color: Color) {
+        self.commands.push(DrawCommand::Line {
+            from: vec2(x1, y1),
+            to: vec2(x2, y2),
+            thickness,
+            color,
+        });
+    }
+
+    fn circle(&mut self, x: f32, y: f32, r: f32, color: Color) {
+        self.commands.push(DrawCommand::Circle {
+            center: vec2(x, y),
+            r,
+            color,
+        });
+    }
+
+    fn circle_lines(&mut self, x: f32, y: f32, r: f32, thickness: f32, color: Color) {
+        self.commands.push(DrawCommand::CircleLines {
+            center: vec2(x, y),
+            r,
+            thickness,
+            color,
+        });
+    }
+
+    fn triangle(&mut self, a: Vec2, b: Vec2, c: Vec2, color: Color) {
+        self.commands.push(DrawCommand::Triangle {
+            points: [a, b, c],
+            color,
+        });
+    }
+
+    fn text(&mut self, text: &str, x: f32, y: f32, font_size: f32, color: Color) {
+        self.commands.push(DrawCommand::Text {
+            text: text.to_string(),
+            position: vec2(x, y),
+            font_size,
+            color,
+        });
+    }
+
+    fn textured_polygon(
+        &mut self,
+        _texture: &Texture2D,
+        points: &[Vec2],
+        _uvs: &[Vec2],
+        _triangles: &[[usize; 3]],
+    ) {
+        self.commands.push(DrawCommand::TexturedPolygon {
+            points: points.to_vec(),
+        });
+    }
+}
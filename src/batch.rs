@@ -0,0 +1,167 @@
+//! A mesh batch that collects the untextured primitives of a frame and submits them with as few draw calls as possible.<br>
+//! The buffers are reused between frames, so no allocations happen once they have grown to the scene size.
+
+use std::f32::consts::TAU;
+
+use macroquad::{
+    color::Color,
+    math::{vec2, Vec2},
+    models::{draw_mesh, Mesh, Vertex},
+};
+
+// macroquad clamps geometry beyond 10000 vertices and 5000 indices per draw call
+const BATCH_MAX_VERTICES: usize = 8000;
+const BATCH_MAX_INDICES: usize = 4800;
+
+const CIRCLE_MIN_SEGMENTS: usize = 8;
+const CIRCLE_MAX_SEGMENTS: usize = 48;
+
+pub struct MeshBatch {
+    mesh: Mesh,
+}
+
+impl Default for MeshBatch {
+    fn default() -> Self {
+        Self {
+            mesh: Mesh {
+                vertices: Vec::new(),
+                indices: Vec::new(),
+                texture: None,
+            },
+        }
+    }
+}
+
+impl MeshBatch {
+    pub fn new() -> MeshBatch {
+        Self::default()
+    }
+
+    pub fn push_triangle(&mut self, a: Vec2, b: Vec2, c: Vec2, color: Color) {
+        self.reserve(3, 3);
+
+        let base = self.mesh.vertices.len() as u16;
+        self.push_vertex(a, color);
+        self.push_vertex(b, color);
+        self.push_vertex(c, color);
+        self.mesh
+            .indices
+            .extend_from_slice(&[base, base + 1, base + 2]);
+    }
+
+    pub fn push_line(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, thickness: f32, color: Color) {
+        let (dx, dy) = (x2 - x1, y2 - y1);
+        let length = (dx * dx + dy * dy).sqrt();
+
+        if length <= f32::EPSILON {
+            return;
+        }
+
+        // half thickness perpendicular to the line
+        let (nx, ny) = (
+            -dy / length * thickness * 0.5,
+            dx / length * thickness * 0.5,
+        );
+
+        self.push_quad(
+            vec2(x1 + nx, y1 + ny),
+            vec2(x2 + nx, y2 + ny),
+            vec2(x2 - nx, y2 - ny),
+            vec2(x1 - nx, y1 - ny),
+            color,
+        );
+    }
+
+    pub fn push_circle(&mut self, x: f32, y: f32, r: f32, color: Color) {
+        let segments = circle_segments(r);
+        self.reserve(segments + 1, 3 * segments);
+
+        let base = self.mesh.vertices.len() as u16;
+        self.push_vertex(vec2(x, y), color);
+
+        for idx in 0..segments {
+            let (sin, cos) = (TAU * idx as f32 / segments as f32).sin_cos();
+            self.push_vertex(vec2(x + r * cos, y + r * sin), color);
+        }
+
+        for idx in 0..segments as u16 {
+            let next = (idx + 1) % segments as u16;
+            self.mesh
+                .indices
+                .extend_from_slice(&[base, base + 1 + idx, base + 1 + next]);
+        }
+    }
+
+    pub fn push_circle_lines(&mut self, x: f32, y: f32, r: f32, thickness: f32, color: Color) {
+        let segments = circle_segments(r);
+        self.reserve(2 * segments, 6 * segments);
+
+        let base = self.mesh.vertices.len() as u16;
+        let (inner, outer) = ((r - thickness * 0.5).max(0.), r + thickness * 0.5);
+
+        for idx in 0..segments {
+            let (sin, cos) = (TAU * idx as f32 / segments as f32).sin_cos();
+            self.push_vertex(vec2(x + inner * cos, y + inner * sin), color);
+            self.push_vertex(vec2(x + outer * cos, y + outer * sin), color);
+        }
+
+        for idx in 0..segments as u16 {
+            let next = (idx + 1) % segments as u16;
+            let (i0, o0, i1, o1) = (2 * idx, 2 * idx + 1, 2 * next, 2 * next + 1);
+            self.mesh.indices.extend_from_slice(&[
+                base + i0,
+                base + o0,
+                base + o1,
+                base + i0,
+                base + o1,
+                base + i1,
+            ]);
+        }
+    }
+
+    /// Submits all collected primitives. Has to be called before anything else gets drawn on top.
+    pub fn flush(&mut self) {
+        if self.mesh.indices.is_empty() {
+            return;
+        }
+
+        draw_mesh(&self.mesh);
+
+        self.mesh.vertices.clear();
+        self.mesh.indices.clear();
+    }
+}
+
+impl MeshBatch {
+    fn push_quad(&mut self, a: Vec2, b: Vec2, c: Vec2, d: Vec2, color: Color) {
+        self.reserve(4, 6);
+
+        let base = self.mesh.vertices.len() as u16;
+        self.push_vertex(a, color);
+        self.push_vertex(b, color);
+        self.push_vertex(c, color);
+        self.push_vertex(d, color);
+        self.mesh
+            .indices
+            .extend_from_slice(&[base, base + 1, base + 2, base, base + 2, base + 3]);
+    }
+
+    fn push_vertex(&mut self, position: Vec2, color: Color) {
+        self.mesh
+            .vertices
+            .push(Vertex::new(position.x, position.y, 0., 0., 0., color));
+    }
+
+    /// Flushes the batch iff the upcoming primitive would exceed the draw call limits.
+    fn reserve(&mut self, vertices: usize, indices: usize) {
+        if self.mesh.vertices.len() + vertices > BATCH_MAX_VERTICES
+            || self.mesh.indices.len() + indices > BATCH_MAX_INDICES
+        {
+            self.flush();
+        }
+    }
+}
+
+fn circle_segments(r: f32) -> usize {
+    ((r * 0.5) as usize).clamp(CIRCLE_MIN_SEGMENTS, CIRCLE_MAX_SEGMENTS)
+}
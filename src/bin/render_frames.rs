@@ -0,0 +1,121 @@
+//! Renders a demo scene to a sequence of PNG frames without a display or GPU.<br>
+//! Usage: `cargo run --release --bin render_frames -- <scene> <frames> [updates per frame] [output dir] [width] [height] [--metrics <path>]`<br>
+//! The frames can be turned into a video e.g. via `ffmpeg -i frames/frame_%05d.png video.mp4`.
+
+use std::{env, fs, process, time::Instant};
+
+use rustycs::environment::force::{Force, GRAVITY_EARTH};
+
+use rustycs_macroquad_demo::{sim_tracker::ShowDebug::*, *};
+
+const TICK_RATE: f32 = 256.;
+const FORCE: Force = GRAVITY_EARTH;
+
+fn main() {
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    let metrics_path = take_metrics_arg(&mut args);
+
+    if args.len() < 2 {
+        eprintln!(
+            "usage: render_frames <scene> <frames> [updates per frame] [output dir] [width] [height] [--metrics <path>]\navailable scenes: {}",
+            WorldFactory::DEMO_SCENES.join(", ")
+        );
+        process::exit(1);
+    }
+
+    let frames: u32 = parse_arg(&args, 1, 0);
+    let updates_per_frame: u32 = parse_arg(&args, 2, 4);
+    let out_dir = args.get(3).map_or("frames", String::as_str);
+    let width: u16 = parse_arg(&args, 4, 1440);
+    let height: u16 = parse_arg(&args, 5, 900);
+
+    let factory = WorldFactory::new(TICK_RATE, FORCE);
+
+    let Some(mut scene) = factory.demo_by_name(&args[0]) else {
+        eprintln!(
+            "unknown scene '{}', available scenes: {}",
+            args[0],
+            WorldFactory::DEMO_SCENES.join(", ")
+        );
+        process::exit(1);
+    };
+
+    let mut metrics: Option<MetricsLog> = metrics_path.map(|path| {
+        MetricsLog::create(&path, scene.gravity).unwrap_or_else(|err| {
+            eprintln!("could not create metrics log {path}: {err}");
+            process::exit(1);
+        })
+    });
+
+    let (mut world, bg_color, mut spawners, styles, mut despawner) = scene.extract();
+    world.set_collision_precision(25);
+
+    let state = SimulationState {
+        debug_information: Hidden,
+        ..SimulationState::new(world.get_delta_time())
+    };
+    let contacts = ContactTracker::default();
+    let mut raster = RasterBackend::new(width, height);
+
+    if let Err(err) = fs::create_dir_all(out_dir) {
+        eprintln!("could not create output directory {out_dir}: {err}");
+        process::exit(1);
+    }
+
+    let mut nr_of_updates: u32 = 0;
+
+    for frame in 0..frames {
+        for _ in 0..updates_per_frame {
+            let update_start = Instant::now();
+            world.update();
+            let update_duration = update_start.elapsed().as_secs_f32();
+            nr_of_updates += 1;
+
+            let now = nr_of_updates as f32 * world.get_delta_time();
+            despawner.apply(&mut world, now);
+
+            for spawner in &mut spawners {
+                for body in spawner.spawn_due(now) {
+                    despawner.add_body(&mut world, body, spawner.lifetime, now);
+                }
+            }
+
+            // after despawning and spawning, like the windowed demo, so both logs can be compared
+            if let Some(log) = &mut metrics {
+                if let Err(err) = log.record(nr_of_updates, update_duration, &world) {
+                    eprintln!("could not write metrics: {err}");
+                    process::exit(1);
+                }
+            }
+        }
+
+        render_world(
+            &mut raster,
+            &world,
+            (0., 0.),
+            &state,
+            &contacts,
+            &styles,
+            bg_color,
+        );
+
+        raster.save_png(&format!("{out_dir}/frame_{frame:05}.png"));
+    }
+
+    if let Some(Err(err)) = metrics.as_mut().map(MetricsLog::flush) {
+        eprintln!("could not write metrics: {err}");
+        process::exit(1);
+    }
+
+    println!("rendered {frames} frames to {out_dir}");
+}
+
+fn parse_arg<T: std::str::FromStr>(args: &[String], idx: usize, default: T) -> T {
+    match args.get(idx) {
+        Some(arg) => arg.parse().unwrap_or_else(|_| {
+            eprintln!("invalid argument '{arg}'");
+            process::exit(1);
+        }),
+        None => default,
+    }
+}
@@ -0,0 +1,126 @@
+//! Monitors whether a scene conserves energy and momentum, to spot solver errors.<br>
+//! Drift is measured relative to a baseline, that gets reset whenever bodies are added or removed,
+//! since spawning changes the totals without any solver involvement.
+
+use std::collections::VecDeque;
+
+use rustycs::{environment::world::World, math::Vector2};
+
+use crate::rustycs_utility as util;
+
+/// Relative change of the total energy between two ticks, above which the tick gets flagged.
+pub const ENERGY_JUMP_THRESHOLD: f32 = 0.05;
+
+/// Number of flagged ticks that are kept.
+const MAX_FLAGGED_TICKS: usize = 16;
+
+/// Conserved quantities of all dynamic bodies at one tick.
+#[derive(Clone, Copy)]
+pub struct ConservationSample {
+    pub tick: u32,
+    pub nr_of_bodies: usize,
+    pub kinetic: f32,
+    pub potential: f32,
+    pub momentum: Vector2,
+    pub angular_momentum: f32,
+}
+
+impl ConservationSample {
+    pub fn measure(tick: u32, world: &World, gravity: Vector2) -> ConservationSample {
+        let bodies = world.get_bodies();
+
+        ConservationSample {
+            tick,
+            nr_of_bodies: bodies.len(),
+            kinetic: util::kinetic_energy(bodies),
+            potential: util::potential_energy(bodies, world.get_attractors(), gravity),
+            momentum: util::linear_momentum(bodies),
+            angular_momentum: util::angular_momentum(bodies),
+        }
+    }
+
+    pub fn total_energy(&self) -> f32 {
+        self.kinetic + self.potential
+    }
+}
+
+/// Change of the conserved quantities since the baseline.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Drift {
+    pub energy: f32,
+    /// energy drift relative to the baseline energy, `None` if the baseline energy is zero
+    pub relative_energy: Option<f32>,
+    pub momentum: f32,
+    pub angular_momentum: f32,
+}
+
+pub struct ConservationMonitor {
+    pub gravity: Vector2,
+    /// relative energy change between two ticks, that counts as a jump
+    pub threshold: f32,
+    pub baseline: Option<ConservationSample>,
+    pub current: Option<ConservationSample>,
+    /// ticks with an energy jump beyond the threshold, most recent last
+    pub flagged_ticks: VecDeque<u32>,
+    pub nr_of_jumps: u32,
+}
+
+impl ConservationMonitor {
+    pub fn new(gravity: Vector2, threshold: f32) -> ConservationMonitor {
+        ConservationMonitor {
+            gravity,
+            threshold,
+            baseline: None,
+            current: None,
+            flagged_ticks: VecDeque::with_capacity(MAX_FLAGGED_TICKS),
+            nr_of_jumps: 0,
+        }
+    }
+
+    /// Measures the world after the update of the given tick and flags it, if the energy jumped.<br>
+    /// `bodies_changed` reports that bodies were spawned or removed during the tick, which resets the baseline.
+    pub fn record(&mut self, tick: u32, world: &World, bodies_changed: bool) {
+        let sample = ConservationSample::measure(tick, world, self.gravity);
+
+        match self.current {
+            // the body count also catches bodies added elsewhere, e.g. by the user
+            Some(last) if !bodies_changed && last.nr_of_bodies == sample.nr_of_bodies => {
+                let (before, after) = (last.total_energy(), sample.total_energy());
+                let scale = before.abs().max(f32::EPSILON);
+
+                if (after - before).abs() / scale > self.threshold {
+                    self.flag(tick);
+                }
+            }
+            _ => self.baseline = Some(sample),
+        }
+
+        self.current = Some(sample);
+    }
+
+    pub fn drift(&self) -> Option<Drift> {
+        let (base, now) = (self.baseline?, self.current?);
+        let energy = now.total_energy() - base.total_energy();
+        let (dpx, dpy) = (
+            now.momentum.x - base.momentum.x,
+            now.momentum.y - base.momentum.y,
+        );
+
+        Some(Drift {
+            energy,
+            relative_energy: (base.total_energy().abs() > f32::EPSILON)
+                .then_some(energy / base.total_energy().abs()),
+            momentum: (dpx * dpx + dpy * dpy).sqrt(),
+            angular_momentum: now.angular_momentum - base.angular_momentum,
+        })
+    }
+
+    fn flag(&mut self, tick: u32) {
+        if self.flagged_ticks.len() == MAX_FLAGGED_TICKS {
+            self.flagged_ticks.pop_front();
+        }
+
+        self.flagged_ticks.push_back(tick);
+        self.nr_of_jumps += 1;
+    }
+}
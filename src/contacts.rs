@@ -0,0 +1,247 @@
+//! Contact detection used for debugging the collision resolution of a rustycs-based world.<br>
+//! The engine only exposes the contact points of the last update, so normals, penetration depth
+//! and impulses are reconstructed here via the separating axis theorem on the current body state.
+
+use rustycs::{
+    entities::body::{Body, BodyType::*},
+    environment::world::World,
+    math::Vector2,
+    shapes::Shape::*,
+};
+
+use crate::rustycs_utility::{dot, length_sq, normalize, scale, sub};
+
+#[derive(Clone, Debug)]
+pub struct Contact {
+    /// index of the first body within `world.get_bodies()`
+    pub body_a: usize,
+    /// index of the second body within `world.get_bodies()`
+    pub body_b: usize,
+    /// deepest point of `body_b` within `body_a`, in world coordinates
+    pub point: Vector2,
+    /// unit normal pointing from `body_a` towards `body_b`
+    pub normal: Vector2,
+    /// penetration depth along the normal in meters
+    pub depth: f32,
+    /// estimated magnitude of the normal impulse that was needed to stop the approach of both bodies
+    pub impulse: f32,
+}
+
+/// Keeps track of the contacts of a world between updates.<br>
+/// Velocities have to be recorded before `world.update()` to estimate the applied impulses afterwards,
+/// bodies removed in between have to be reported via `remove_bodies`.
+#[derive(Default)]
+pub struct ContactTracker {
+    pub contacts: Vec<Contact>,
+    velocities: Vec<Vector2>,
+}
+
+impl ContactTracker {
+    pub fn record_velocities(&mut self, world: &World) {
+        self.velocities.clear();
+        self.velocities
+            .extend(world.get_bodies().iter().map(|b| b.transform.velocity));
+    }
+
+    /// Drops the recorded velocities of removed bodies, so the remaining ones keep matching their bodies.<br>
+    /// `indices` are the indices the bodies had before their removal, in ascending order.
+    pub fn remove_bodies(&mut self, indices: &[usize]) {
+        for &idx in indices.iter().rev() {
+            if idx < self.velocities.len() {
+                self.velocities.remove(idx);
+            }
+        }
+    }
+
+    pub fn detect(&mut self, world: &World) {
+        self.contacts.clear();
+
+        let bodies = world.get_bodies();
+        let shapes: Vec<ContactShape> = bodies.iter().map(ContactShape::from_body).collect();
+
+        for a in 0..bodies.len() {
+            for b in (a + 1)..bodies.len() {
+                if bodies[a].body_type == Static && bodies[b].body_type == Static {
+                    continue;
+                }
+
+                if !hitboxes_overlap(&bodies[a], &bodies[b]) {
+                    continue;
+                }
+
+                if let Some((normal, depth)) = separating_axis_test(&shapes[a], &shapes[b]) {
+                    let point = shapes[b].support(scale(normal, -1.));
+                    let impulse = self.estimate_impulse(bodies, a, b, normal);
+
+                    self.contacts.push(Contact {
+                        body_a: a,
+                        body_b: b,
+                        point,
+                        normal,
+                        depth,
+                        impulse,
+                    });
+                }
+            }
+        }
+    }
+
+    fn estimate_impulse(&self, bodies: &[Body], a: usize, b: usize, normal: Vector2) -> f32 {
+        let (Some(vel_a), Some(vel_b)) = (self.velocities.get(a), self.velocities.get(b)) else {
+            return 0.;
+        };
+
+        let inv_mass = inverse_mass(&bodies[a]) + inverse_mass(&bodies[b]);
+        let approach = -dot(sub(*vel_b, *vel_a), normal);
+
+        if inv_mass == 0. || approach <= 0. {
+            return 0.;
+        }
+
+        approach / inv_mass
+    }
+}
+
+// ---------------------- SHAPES ----------------------
+enum ContactShape {
+    Circle { center: Vector2, r: f32 },
+    Polygon(Vec<Vector2>),
+}
+
+impl ContactShape {
+    fn from_body(body: &Body) -> ContactShape {
+        let loc = body.transform.location;
+
+        match &body.shape {
+            Circle(c) => ContactShape::Circle {
+                center: loc,
+                r: c.r,
+            },
+            AABB(_) | Polygon(_) => ContactShape::Polygon(
+                body.get_vertices_as_vec()
+                    .into_iter()
+                    .map(|v| v + loc)
+                    .collect(),
+            ),
+        }
+    }
+
+    fn center(&self) -> Vector2 {
+        match self {
+            ContactShape::Circle { center, .. } => *center,
+            ContactShape::Polygon(vertices) => {
+                let n = vertices.len() as f32;
+                let (x, y) = vertices
+                    .iter()
+                    .fold((0., 0.), |(x, y), v| (x + v.x, y + v.y));
+                Vector2::new(x / n, y / n)
+            }
+        }
+    }
+
+    fn project(&self, axis: Vector2) -> (f32, f32) {
+        match self {
+            ContactShape::Circle { center, r } => {
+                let c = dot(*center, axis);
+                (c - r, c + r)
+            }
+            ContactShape::Polygon(vertices) => vertices
+                .iter()
+                .map(|v| dot(*v, axis))
+                .fold((f32::MAX, f32::MIN), |(min, max), p| {
+                    (min.min(p), max.max(p))
+                }),
+        }
+    }
+
+    /// Point of the shape that lies furthest into the given direction.
+    fn support(&self, direction: Vector2) -> Vector2 {
+        match self {
+            ContactShape::Circle { center, r } => *center + scale(direction, *r),
+            ContactShape::Polygon(vertices) => *vertices
+                .iter()
+                .max_by(|a, b| dot(**a, direction).total_cmp(&dot(**b, direction)))
+                .expect("polygons have at least 3 vertices"),
+        }
+    }
+
+    fn axes(&self, other: &ContactShape) -> Vec<Vector2> {
+        match self {
+            ContactShape::Polygon(vertices) => {
+                let n = vertices.len();
+                (0..n)
+                    .map(|idx| {
+                        let edge = sub(vertices[(idx + 1) % n], vertices[idx]);
+                        Vector2::new(-edge.y, edge.x)
+                    })
+                    .collect()
+            }
+            ContactShape::Circle { center, .. } => match other {
+                ContactShape::Circle {
+                    center: other_center,
+                    ..
+                } => vec![sub(*other_center, *center)],
+                ContactShape::Polygon(vertices) => vertices
+                    .iter()
+                    .min_by(|a, b| {
+                        length_sq(sub(**a, *center)).total_cmp(&length_sq(sub(**b, *center)))
+                    })
+                    .map(|closest| vec![sub(*closest, *center)])
+                    .unwrap_or_default(),
+            },
+        }
+    }
+}
+
+/// Returns the axis of minimal overlap (pointing from `a` to `b`) and the overlap, iff both shapes intersect.
+fn separating_axis_test(a: &ContactShape, b: &ContactShape) -> Option<(Vector2, f32)> {
+    let mut min_overlap = f32::MAX;
+    let mut min_axis = Vector2::new(0., 0.);
+
+    for axis in a.axes(b).into_iter().chain(b.axes(a)) {
+        let Some(axis) = normalize(axis) else {
+            continue;
+        };
+
+        let (a_min, a_max) = a.project(axis);
+        let (b_min, b_max) = b.project(axis);
+        let overlap = a_max.min(b_max) - a_min.max(b_min);
+
+        if overlap <= 0. {
+            return None;
+        }
+
+        if overlap < min_overlap {
+            min_overlap = overlap;
+            min_axis = axis;
+        }
+    }
+
+    if min_overlap == f32::MAX {
+        return None;
+    }
+
+    if dot(sub(b.center(), a.center()), min_axis) < 0. {
+        min_axis = scale(min_axis, -1.);
+    }
+
+    Some((min_axis, min_overlap))
+}
+
+fn hitboxes_overlap(a: &Body, b: &Body) -> bool {
+    // hitboxes are stored relative to the body location
+    let (la, lb) = (a.transform.location, b.transform.location);
+
+    la.x + a.hitbox.min.x <= lb.x + b.hitbox.max.x
+        && la.x + a.hitbox.max.x >= lb.x + b.hitbox.min.x
+        && la.y + a.hitbox.min.y <= lb.y + b.hitbox.max.y
+        && la.y + a.hitbox.max.y >= lb.y + b.hitbox.min.y
+}
+
+fn inverse_mass(body: &Body) -> f32 {
+    if body.body_type == Static || body.mass == 0. {
+        0.
+    } else {
+        1. / body.mass
+    }
+}
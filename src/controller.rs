@@ -8,7 +8,10 @@ use std::time::Instant;
 use rand::Rng;
 
 use macroquad::{
-    input::KeyCode,
+    input::{
+        is_mouse_button_down, is_mouse_button_pressed, is_mouse_button_released, KeyCode,
+        MouseButton,
+    },
     prelude::{is_key_down, mouse_position, screen_height, screen_width},
 };
 
@@ -17,15 +20,20 @@ use rustycs::{
     entities::{
         attractor::{Attractor, AttractorType::*},
         body::Body,
-        material::{self},
+        material::{self, Material},
     },
     environment::world::World,
     math::vector2::Vector2,
 };
 
 use crate::{
-    rustycs_utility as util,
-    sim_tracker::{SimulationMode::*, SimulationState},
+    contacts::ContactTracker,
+    polygon_validation::{checked_polygon, PolygonError},
+    rustycs_utility::{self as util, BodySpawner},
+    sim_tracker::{EditMode, ShowDebug::*, SimulationMode::*, SimulationState},
+    spawn_region::SpawnRegion,
+    svg::SvgSpace,
+    templates::SpawnerPreset,
 };
 
 pub const MOVE_CAMERA_UP: KeyCode = KeyCode::W;
@@ -43,9 +51,211 @@ pub const SPAWN_ATTRACTOR: KeyCode = KeyCode::Key5;
 pub const TOGGLE_TEXT: KeyCode = KeyCode::T;
 pub const OPEN_MENU_AND_PAUSE: KeyCode = KeyCode::Escape;
 pub const TOGGLE_HITBOXES: KeyCode = KeyCode::H;
+pub const TOGGLE_SPRITES: KeyCode = KeyCode::X;
 pub const TOGGLE_COLLISION_POINTS: KeyCode = KeyCode::C;
+pub const TOGGLE_CONTACT_DETAILS: KeyCode = KeyCode::K;
 pub const TOGGLE_GRID: KeyCode = KeyCode::G;
+pub const TOGGLE_GRAPHS: KeyCode = KeyCode::M;
+pub const TOGGLE_CONSERVATION: KeyCode = KeyCode::E;
+pub const TOGGLE_ATTRACTOR_FIELD: KeyCode = KeyCode::F;
 pub const WORLD_UPDATE: KeyCode = KeyCode::U;
+pub const RESET_STATS: KeyCode = KeyCode::B;
+pub const TOGGLE_SPAWNERS: KeyCode = KeyCode::N;
+pub const EXPORT_SVG_SCREEN: KeyCode = KeyCode::P;
+pub const EXPORT_SVG_WORLD: KeyCode = KeyCode::O;
+pub const CYCLE_EDIT_MODE: KeyCode = KeyCode::Q;
+pub const CYCLE_SPAWNER_PRESET: KeyCode = KeyCode::Z;
+pub const CYCLE_SPAWNER_AMOUNT: KeyCode = KeyCode::V;
+pub const SPAWNER_FREQUENCY_DOWN: KeyCode = KeyCode::Minus;
+pub const SPAWNER_FREQUENCY_UP: KeyCode = KeyCode::Equal;
+pub const DELETE_SPAWNER: KeyCode = KeyCode::Delete;
+pub const SPAWN_TOOL_BODY: KeyCode = KeyCode::Space;
+pub const CYCLE_TOOL_SHAPE: KeyCode = KeyCode::Tab;
+pub const CYCLE_TOOL_MATERIAL: KeyCode = KeyCode::Y;
+pub const TOOL_WIDTH_DOWN: KeyCode = KeyCode::Left;
+pub const TOOL_WIDTH_UP: KeyCode = KeyCode::Right;
+pub const TOOL_HEIGHT_DOWN: KeyCode = KeyCode::Comma;
+pub const TOOL_HEIGHT_UP: KeyCode = KeyCode::Period;
+pub const ROTATE_TOOL_LEFT: KeyCode = KeyCode::LeftBracket;
+pub const ROTATE_TOOL_RIGHT: KeyCode = KeyCode::RightBracket;
+
+/// Screen distance in pixels, within which a click selects a spawner.
+pub const SPAWNER_PICK_RADIUS: f32 = 12.;
+const MIN_ATTRACTOR_RADIUS: f32 = 0.1;
+const SPAWNER_AMOUNTS: [Option<u32>; 5] = [Some(10), Some(50), Some(100), Some(500), None];
+
+const TOOL_SIZE_STEP: f32 = 1.25;
+const TOOL_MIN_SIZE: f32 = 0.05;
+const TOOL_MAX_SIZE: f32 = 50.;
+const TOOL_ROTATION_STEP: f32 = std::f32::consts::PI / 12.;
+/// `None` picks one of the other materials at random for every body.
+const TOOL_MATERIALS: [(&str, Option<Material>); 6] = [
+    ("default", Some(material::DEFAULT)),
+    ("rubber", Some(material::RUBBER)),
+    ("plastic", Some(material::PLASTIC)),
+    ("stone", Some(material::STONE)),
+    ("metal", Some(material::METAL)),
+    ("random", None),
+];
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum ToolShape {
+    Circle,
+    Aabb,
+    Obb,
+    Polygon,
+}
+
+impl ToolShape {
+    pub fn next(self) -> ToolShape {
+        use ToolShape::*;
+
+        match self {
+            Circle => Aabb,
+            Aabb => Obb,
+            Obb => Polygon,
+            Polygon => Circle,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        use ToolShape::*;
+
+        match self {
+            Circle => "circle",
+            Aabb => "AABB",
+            Obb => "OBB",
+            Polygon => "polygon",
+        }
+    }
+}
+
+/// Bodies placed under the cursor with the spawn keys, configured via the palette of the pause menu.<br>
+/// Circles use half the width as radius and polygons the width as scale, AABBs ignore the rotation.<br>
+/// OBBs and polygons are built from their rotated vertices and circles get their reference vertex rotated,
+/// so vertices and hitbox match the initial rotation.
+pub struct SpawnTool {
+    pub shape: ToolShape,
+    pub width: f32,
+    pub height: f32,
+    /// index into `TOOL_MATERIALS`
+    pub material: usize,
+    /// initial rotation in radians
+    pub rotation: f32,
+}
+
+impl Default for SpawnTool {
+    fn default() -> Self {
+        Self {
+            shape: ToolShape::Circle,
+            width: 1.,
+            height: 1.,
+            material: 0,
+            rotation: 0.,
+        }
+    }
+}
+
+impl SpawnTool {
+    pub fn material_name(&self) -> &'static str {
+        TOOL_MATERIALS[self.material].0
+    }
+
+    /// Vertices of the current shape relative to its location and rotated by the initial rotation,
+    /// empty for circles.
+    pub fn outline(&self) -> Vec<Vector2> {
+        let (hw, hh) = (0.5 * self.width, 0.5 * self.height);
+        let rectangle = vec![
+            Vector2::new(-hw, hh),
+            Vector2::new(hw, hh),
+            Vector2::new(hw, -hh),
+            Vector2::new(-hw, -hh),
+        ];
+
+        let (vertices, rotation) = match self.shape {
+            ToolShape::Circle => return Vec::new(),
+            ToolShape::Aabb => (rectangle, 0.),
+            ToolShape::Obb => (rectangle, self.rotation),
+            ToolShape::Polygon => (util::poly_complex(self.width), self.rotation),
+        };
+
+        vertices
+            .into_iter()
+            .map(|v| util::rotate(v, rotation))
+            .collect()
+    }
+
+    /// Fails with the reason, if the current polygon is invalid.
+    pub fn build(&self, location: Vector2) -> Result<Body, PolygonError> {
+        let material = TOOL_MATERIALS[self.material]
+            .1
+            .clone()
+            .unwrap_or_else(random_material);
+        let (x, y) = (location.x, location.y);
+
+        match self.shape {
+            ToolShape::Circle => util::rotated_body(
+                &Body::circle(x, y, 0.5 * self.width, material),
+                self.rotation,
+            ),
+            ToolShape::Aabb => Ok(Body::aabb(x, y, self.width, self.height, material)),
+            ToolShape::Obb | ToolShape::Polygon => checked_polygon(x, y, self.outline(), material),
+        }
+    }
+
+    fn scale_width(&mut self, factor: f32) {
+        self.width = (self.width * factor).clamp(TOOL_MIN_SIZE, TOOL_MAX_SIZE);
+    }
+
+    fn scale_height(&mut self, factor: f32) {
+        self.height = (self.height * factor).clamp(TOOL_MIN_SIZE, TOOL_MAX_SIZE);
+    }
+}
+
+/// Configuration of spawners placed with the mouse.
+pub struct SpawnerSettings {
+    pub preset: SpawnerPreset,
+    pub frequency_in_hz: f32,
+    /// index into `SPAWNER_AMOUNTS`
+    pub amount: usize,
+}
+
+impl Default for SpawnerSettings {
+    fn default() -> Self {
+        Self {
+            preset: SpawnerPreset::default(),
+            frequency_in_hz: 4.,
+            amount: 1,
+        }
+    }
+}
+
+impl SpawnerSettings {
+    /// `None` if unlimited.
+    pub fn amount(&self) -> Option<u32> {
+        SPAWNER_AMOUNTS[self.amount]
+    }
+
+    /// New spawner at `location`, that starts spawning one period after the simulation time `now`.<br>
+    /// It is seeded by `now`, so replaying the same placements spawns the same bodies.
+    pub fn create(&self, location: Vector2, now: f32) -> BodySpawner {
+        let mut spawner = BodySpawner::new_weighted(
+            self.preset.templates(),
+            location,
+            self.amount(),
+            self.frequency_in_hz,
+        )
+        .with_region(SpawnRegion::Circle {
+            center: location,
+            radius: 0.5,
+        })
+        .with_poisson_disk(0.3)
+        .with_seed(u64::from(now.to_bits()));
+
+        spawner.last_spawn = now;
+        spawner
+    }
+}
 
 pub struct UserController {
     pub user_actions: Vec<KeyCode>,
@@ -54,6 +264,8 @@ pub struct UserController {
     pub zoom_speed: f32,
     pub sampling_rate: f32,
     pub sampling_instant: Instant,
+    pub spawner_settings: SpawnerSettings,
+    pub spawn_tool: SpawnTool,
 }
 
 impl UserController {
@@ -70,12 +282,36 @@ impl UserController {
             SPAWN_OBB,
             SPAWN_POLYGON,
             SPAWN_ATTRACTOR,
+            SPAWN_TOOL_BODY,
+            CYCLE_TOOL_SHAPE,
+            CYCLE_TOOL_MATERIAL,
+            TOOL_WIDTH_DOWN,
+            TOOL_WIDTH_UP,
+            TOOL_HEIGHT_DOWN,
+            TOOL_HEIGHT_UP,
+            ROTATE_TOOL_LEFT,
+            ROTATE_TOOL_RIGHT,
             TOGGLE_TEXT,
             TOGGLE_GRID,
+            TOGGLE_GRAPHS,
+            TOGGLE_CONSERVATION,
+            RESET_STATS,
+            TOGGLE_SPAWNERS,
+            TOGGLE_ATTRACTOR_FIELD,
             TOGGLE_HITBOXES,
+            TOGGLE_SPRITES,
             TOGGLE_COLLISION_POINTS,
+            TOGGLE_CONTACT_DETAILS,
             WORLD_UPDATE,
             RESET_CAMERA_POS,
+            EXPORT_SVG_SCREEN,
+            EXPORT_SVG_WORLD,
+            CYCLE_EDIT_MODE,
+            CYCLE_SPAWNER_PRESET,
+            CYCLE_SPAWNER_AMOUNT,
+            SPAWNER_FREQUENCY_DOWN,
+            SPAWNER_FREQUENCY_UP,
+            DELETE_SPAWNER,
         ];
 
         UserController {
@@ -85,6 +321,8 @@ impl UserController {
             zoom_speed,
             sampling_rate: 1. / 120.,
             sampling_instant: Instant::now(),
+            spawner_settings: SpawnerSettings::default(),
+            spawn_tool: SpawnTool::default(),
         }
     }
 }
@@ -110,6 +348,8 @@ impl UserController {
     pub fn handle_current_actions(
         &mut self,
         world: &mut World,
+        spawners: &mut Vec<BodySpawner>,
+        contacts: &mut ContactTracker,
         offset_x: &mut f32,
         offset_y: &mut f32,
         state: &mut SimulationState,
@@ -130,20 +370,20 @@ impl UserController {
 
         for action in &self.active_actions {
             match *action {
-                SPAWN_AABB => {
-                    spawn_aabb(world, world_position);
-                    added = true;
-                }
-                SPAWN_OBB => {
-                    spawn_obb(world, world_position);
-                    added = true;
-                }
-                SPAWN_CIRCLE => {
-                    spawn_circle(world, world_position);
-                    added = true;
-                }
-                SPAWN_POLYGON => {
-                    spawn_polygon(world, world_position);
+                SPAWN_CIRCLE | SPAWN_AABB | SPAWN_OBB | SPAWN_POLYGON | SPAWN_TOOL_BODY => {
+                    self.spawn_tool.shape = match *action {
+                        SPAWN_CIRCLE => ToolShape::Circle,
+                        SPAWN_AABB => ToolShape::Aabb,
+                        SPAWN_OBB => ToolShape::Obb,
+                        SPAWN_POLYGON => ToolShape::Polygon,
+                        _ => self.spawn_tool.shape,
+                    };
+
+                    match self.spawn_tool.build(world_position) {
+                        Ok(body) => world.add_body(body),
+                        Err(err) => eprintln!("could not spawn polygon: {err}"),
+                    }
+
                     added = true;
                 }
                 SPAWN_ATTRACTOR => {
@@ -162,11 +402,42 @@ impl UserController {
                 }
                 WORLD_UPDATE => {
                     if state.simulation == Paused && state.atomic_update_allowed() {
+                        if state.contact_details == Visible {
+                            contacts.record_velocities(world);
+                        }
+
+                        let update_start = Instant::now();
                         world.update();
+                        state
+                            .timings
+                            .physics
+                            .push(update_start.elapsed().as_secs_f32());
                         state.nr_of_updates += 1;
                         state.update_instant = Instant::now();
                     }
                 }
+                CYCLE_TOOL_SHAPE | CYCLE_TOOL_MATERIAL | TOOL_WIDTH_DOWN | TOOL_WIDTH_UP
+                | TOOL_HEIGHT_DOWN | TOOL_HEIGHT_UP | ROTATE_TOOL_LEFT | ROTATE_TOOL_RIGHT => {
+                    // the palette is part of the pause menu
+                    if state.simulation == Paused && state.debug_toggable() {
+                        let tool = &mut self.spawn_tool;
+
+                        match *action {
+                            CYCLE_TOOL_SHAPE => tool.shape = tool.shape.next(),
+                            CYCLE_TOOL_MATERIAL => {
+                                tool.material = (tool.material + 1) % TOOL_MATERIALS.len()
+                            }
+                            TOOL_WIDTH_DOWN => tool.scale_width(1. / TOOL_SIZE_STEP),
+                            TOOL_WIDTH_UP => tool.scale_width(TOOL_SIZE_STEP),
+                            TOOL_HEIGHT_DOWN => tool.scale_height(1. / TOOL_SIZE_STEP),
+                            TOOL_HEIGHT_UP => tool.scale_height(TOOL_SIZE_STEP),
+                            ROTATE_TOOL_LEFT => tool.rotation += TOOL_ROTATION_STEP,
+                            _ => tool.rotation -= TOOL_ROTATION_STEP,
+                        }
+
+                        state.debug_instant = Instant::now();
+                    }
+                }
                 any_toggle => {
                     if state.debug_toggable() {
                         let mut toggled = true;
@@ -174,8 +445,67 @@ impl UserController {
                         match any_toggle {
                             TOGGLE_TEXT => state.debug_information.toggle(),
                             TOGGLE_GRID => state.debug_grid.toggle(),
+                            TOGGLE_GRAPHS => state.graphs.toggle(),
+                            TOGGLE_CONSERVATION => state.conservation.toggle(),
+                            RESET_STATS => state.timings.reset(),
+                            TOGGLE_SPAWNERS => {
+                                // pauses all spawners, unless all of them are paused already
+                                let resume = spawners.iter().all(|s| s.paused);
+
+                                for spawner in spawners.iter_mut() {
+                                    if resume {
+                                        spawner.resume();
+                                    } else {
+                                        spawner.pause();
+                                    }
+                                }
+                            }
+                            TOGGLE_ATTRACTOR_FIELD => state.attractor_field.toggle(),
                             TOGGLE_COLLISION_POINTS => state.collision_points.toggle(),
+                            TOGGLE_CONTACT_DETAILS => state.contact_details.toggle(),
                             TOGGLE_HITBOXES => state.hitboxes.toggle(),
+                            TOGGLE_SPRITES => state.sprites.toggle(),
+                            EXPORT_SVG_SCREEN => state.svg_export = Some(SvgSpace::Screen),
+                            EXPORT_SVG_WORLD => {
+                                let (x, y) = world.world_to_screen(
+                                    Vector2::new(0., 0.),
+                                    screen_width(),
+                                    screen_height(),
+                                );
+
+                                state.svg_export = Some(SvgSpace::World {
+                                    ptm_ratio: world.get_ptm_ratio(),
+                                    origin: (x + *offset_x, y + *offset_y),
+                                });
+                            }
+                            CYCLE_EDIT_MODE => {
+                                state.edit_mode.toggle();
+                                state.selected_spawner = None;
+                                state.drag_start = None;
+                            }
+                            CYCLE_SPAWNER_PRESET => {
+                                self.spawner_settings.preset = self.spawner_settings.preset.next()
+                            }
+                            CYCLE_SPAWNER_AMOUNT => {
+                                let settings = &mut self.spawner_settings;
+                                settings.amount = (settings.amount + 1) % SPAWNER_AMOUNTS.len();
+                            }
+                            SPAWNER_FREQUENCY_DOWN => {
+                                let settings = &mut self.spawner_settings;
+                                settings.frequency_in_hz =
+                                    (settings.frequency_in_hz * 0.5).max(0.25);
+                            }
+                            SPAWNER_FREQUENCY_UP => {
+                                let settings = &mut self.spawner_settings;
+                                settings.frequency_in_hz = (settings.frequency_in_hz * 2.).min(64.);
+                            }
+                            DELETE_SPAWNER => {
+                                if let Some(idx) = state.selected_spawner.take() {
+                                    if idx < spawners.len() {
+                                        spawners.remove(idx);
+                                    }
+                                }
+                            }
                             _ => toggled = false,
                         }
 
@@ -193,53 +523,76 @@ impl UserController {
     }
 }
 
-fn spawn_circle(w: &mut World, world_position: Vector2) {
+impl UserController {
+    /// Places, selects and moves spawners or places local attractors, depending on the edit mode.<br>
+    /// Has to be called every frame, since mouse clicks are not sampled like the keys.
+    pub fn handle_mouse(
+        &mut self,
+        world: &mut World,
+        spawners: &mut Vec<BodySpawner>,
+        offset: (f32, f32),
+        state: &mut SimulationState,
+    ) {
+        let (mx, my) = mouse_position();
+        let (w, h) = (screen_width(), screen_height());
+        let world_position = world.screen_to_world(mx - offset.0, my - offset.1, w, h);
+
+        match state.edit_mode {
+            EditMode::Inactive => {}
+            EditMode::Spawners => {
+                if is_mouse_button_pressed(MouseButton::Left) {
+                    let hit = spawners.iter().position(|spawner| {
+                        let (sx, sy) = world.world_to_screen(spawner.location(), w, h);
+                        let (dx, dy) = (sx + offset.0 - mx, sy + offset.1 - my);
+                        (dx * dx + dy * dy).sqrt() <= SPAWNER_PICK_RADIUS
+                    });
+
+                    if hit.is_none() {
+                        let now = state.simulation_time();
+                        spawners.push(self.spawner_settings.create(world_position, now));
+                    }
+
+                    state.selected_spawner = hit.or(Some(spawners.len() - 1));
+                    state.drag_start = Some(world_position);
+                } else if is_mouse_button_down(MouseButton::Left) && state.drag_start.is_some() {
+                    if let Some(spawner) =
+                        state.selected_spawner.and_then(|idx| spawners.get_mut(idx))
+                    {
+                        spawner.move_to(world_position);
+                    }
+                }
+
+                if is_mouse_button_released(MouseButton::Left) {
+                    state.drag_start = None;
+                }
+            }
+            EditMode::Attractors => {
+                if is_mouse_button_pressed(MouseButton::Left) {
+                    state.drag_start = Some(world_position);
+                }
+
+                if is_mouse_button_released(MouseButton::Left) {
+                    if let Some(center) = state.drag_start.take() {
+                        let (dx, dy) = (world_position.x - center.x, world_position.y - center.y);
+                        let r = (dx * dx + dy * dy).sqrt().max(MIN_ATTRACTOR_RADIUS);
+
+                        world.add_attractor(Attractor::new(center.x, center.y, r, Local, None));
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn random_material() -> Material {
     let mut rng = rand::thread_rng();
     let mat_id: u8 = rng.gen_range(0..4);
 
-    let material = match mat_id {
+    match mat_id {
         0 => material::PLASTIC,
         1 => material::RUBBER,
         2 => material::STONE,
         _ => material::METAL,
-    };
-
-    w.add_body(Body::circle(
-        world_position.x,
-        world_position.y,
-        0.2,
-        material,
-    ))
-}
-
-fn spawn_aabb(w: &mut World, world_position: Vector2) {
-    w.add_body(Body::aabb(
-        world_position.x,
-        world_position.y,
-        1.0,
-        1.0,
-        material::DEFAULT,
-    ));
-}
-
-fn spawn_obb(w: &mut World, world_position: Vector2) {
-    w.add_body(Body::obb(
-        world_position.x,
-        world_position.y,
-        1.0,
-        1.0,
-        material::DEFAULT,
-    ));
-}
-
-fn spawn_polygon(w: &mut World, world_position: Vector2) {
-    if let Some(poly) = Body::polygon(
-        world_position.x,
-        world_position.y,
-        util::poly_complex(3.0),
-        material::DEFAULT,
-    ) {
-        w.add_body(poly);
     }
 }
 
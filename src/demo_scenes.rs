@@ -1,8 +1,8 @@
 //! Factory struct that provides predefined rustycs scenes for demo purposes.
-use std::mem;
+use std::{f32::consts::PI, mem};
 
-use macroquad::color::{Color, BLACK, WHITE};
-use rand::{thread_rng, Rng};
+use macroquad::color::*;
+use rand::{rngs::StdRng, SeedableRng};
 use rustycs::{
     entities::{
         attractor::*,
@@ -10,14 +10,25 @@ use rustycs::{
         material::{self, Material},
     },
     environment::{force::Force, world::World},
+    math::Vector2,
 };
 
-use crate::rustycs_utility::{self as util, *};
+use crate::{
+    despawn::{Despawner, KillZone},
+    polygon_validation::checked_polygon,
+    rustycs_utility::{self as util, *},
+    spawn_region::SpawnRegion,
+    styles::{BodyStyle, FillMode, StyleRegistry},
+};
 
 pub struct WorldScene {
     pub world: World,
     pub bg_color: Color,
     pub spawners: Vec<BodySpawner>,
+    pub styles: StyleRegistry,
+    pub despawner: Despawner,
+    /// uniform acceleration of the forces added to the world, used to measure potential energy
+    pub gravity: Vector2,
 }
 
 impl WorldScene {
@@ -26,14 +37,34 @@ impl WorldScene {
             world: content.0,
             bg_color: content.1,
             spawners: content.2,
+            styles: StyleRegistry::new(),
+            despawner: Despawner::default(),
+            gravity: Vector2::new(0., 0.),
         }
     }
 
-    pub fn extract(&mut self) -> (World, Color, Vec<BodySpawner>) {
+    pub fn with_gravity(mut self, gravity: Vector2) -> Self {
+        self.gravity = gravity;
+        self
+    }
+
+    pub fn with_styles(mut self, styles: StyleRegistry) -> Self {
+        self.styles = styles;
+        self
+    }
+
+    pub fn with_kill_zones(mut self, zones: Vec<KillZone>) -> Self {
+        self.despawner = Despawner::new(zones);
+        self
+    }
+
+    pub fn extract(&mut self) -> (World, Color, Vec<BodySpawner>, StyleRegistry, Despawner) {
         (
             mem::take(&mut self.world),
             mem::take(&mut self.bg_color),
             mem::take(&mut self.spawners),
+            mem::take(&mut self.styles),
+            mem::take(&mut self.despawner),
         )
     }
 }
@@ -41,14 +72,55 @@ impl WorldScene {
 pub struct WorldFactory {
     tick_rate: f32,
     default_force: Force,
+    default_gravity: Vector2,
 }
 
 impl WorldFactory {
+    /// Scenes with `default_force` measure potential energy with `GRAVITY_EARTH_ACCELERATION`,
+    /// so the force is expected to be `force::GRAVITY_EARTH`.
     pub fn new(tick_rate: f32, default_force: Force) -> WorldFactory {
         Self {
             tick_rate,
             default_force,
+            default_gravity: GRAVITY_EARTH_ACCELERATION,
+        }
+    }
+}
+
+impl WorldFactory {
+    pub const DEMO_SCENES: [&'static str; 8] = [
+        "solar_system",
+        "filling_pool_with_circles",
+        "piling_all_rotating_body_types",
+        "material_differences",
+        "slide_and_fall",
+        "slope",
+        "all_platforms",
+        "fountain",
+    ];
+
+    /// Scalable pile scenes are named by this prefix followed by the number of bodies, e.g. `pile_1000`.
+    pub const PILE_SCENE_PREFIX: &'static str = "pile_";
+
+    /// Demo scene by name (see `DEMO_SCENES` and `PILE_SCENE_PREFIX`), material parameters use `material::DEFAULT`.
+    pub fn demo_by_name(&self, name: &str) -> Option<WorldScene> {
+        if let Some(nr_of_bodies) = name.strip_prefix(Self::PILE_SCENE_PREFIX) {
+            return nr_of_bodies.parse().ok().map(|n| self.demo_pile(n));
         }
+
+        let scene = match name {
+            "solar_system" => self.demo_solar_system(),
+            "filling_pool_with_circles" => self.demo_filling_pool_with_circles(material::DEFAULT),
+            "piling_all_rotating_body_types" => self.demo_piling_all_rotating_body_types(),
+            "material_differences" => self.demo_material_differences(material::DEFAULT),
+            "slide_and_fall" => self.demo_slide_and_fall(),
+            "slope" => self.demo_slope(),
+            "all_platforms" => self.demo_all_platforms(),
+            "fountain" => self.demo_fountain(),
+            _ => return None,
+        };
+
+        Some(scene)
     }
 }
 
@@ -63,7 +135,23 @@ impl WorldFactory {
         w.add_attractor(sun);
         w.add_bodies(planets);
 
-        WorldScene::new((w, BLACK, vec![]))
+        let mut styles = StyleRegistry::new();
+        styles.insert_name("sun", BodyStyle::filled(YELLOW));
+
+        for (name, color) in [
+            ("mercury", BROWN),
+            ("venus", WHITE),
+            ("earth", BLUE),
+            ("mars", RED),
+            ("jupiter", ORANGE),
+            ("saturn", GOLD),
+            ("uranus", SKYBLUE),
+            ("neptune", DARKBLUE),
+        ] {
+            styles.insert_name(name, BodyStyle::filled(color).labelled());
+        }
+
+        WorldScene::new((w, BLACK, vec![])).with_styles(styles)
     }
 
     /// Fills up a bounded platform with 100 circles, where the material of the platform and circles can be chosen.
@@ -82,9 +170,13 @@ impl WorldFactory {
             100,
             30.,
             0.5,
-        );
+        )
+        .with_seed(1);
 
         WorldScene::new((w, WHITE, vec![pool_filler]))
+            .with_gravity(self.default_gravity)
+            .with_styles(StyleRegistry::new().with_fill_mode(FillMode::Filled))
+            .with_kill_zones(play_area())
     }
 
     /// Fills up a bounded platform with 150 random bodies, with random materials and sizes.
@@ -92,26 +184,80 @@ impl WorldFactory {
         let mut w = World::new(self.tick_rate, 40.);
         w.add_force(self.default_force.clone());
 
-        let mut pipeline: Vec<Body> = Vec::new();
-        let mut rng = thread_rng();
-
-        let mut x: f32;
-        let y: f32 = 10.;
-
         w.add_bodies(vec![
             Body::platform_rectangle_aabb(0.0, -3.0, 24.0, 1.0, material::DEFAULT),
             Body::platform_rectangle_aabb(-12.15, 0.0, 0.1, 10.0, material::DEFAULT),
             Body::platform_rectangle_aabb(12.15, 0.0, 0.1, 10.0, material::DEFAULT),
         ]);
 
-        for _ in 0..100 {
-            x = rng.gen_range(-10.0..=10.0);
-            pipeline.push(random_body(x, y, &mut rng))
-        }
-
-        let body_spawner = BodySpawner::new_pipeline(pipeline, 10.);
+        let body_spawner = BodySpawner::new_weighted(
+            random_body_templates(),
+            Vector2::new(0., 10.),
+            Some(100),
+            10.,
+        )
+        .with_region(SpawnRegion::Segment {
+            from: Vector2::new(-10., 10.),
+            to: Vector2::new(10., 10.),
+        })
+        .with_seed(2);
 
         WorldScene::new((w, WHITE, vec![body_spawner]))
+            .with_gravity(self.default_gravity)
+            .with_kill_zones(play_area())
+    }
+
+    /// Scalable variant of `demo_piling_all_rotating_body_types` without a spawner.<br>
+    /// The random bodies start in a grid above the ground, which is widened to fit all of them.
+    pub fn demo_pile(&self, nr_of_bodies: usize) -> WorldScene {
+        // the largest random body, `poly_simple` at scale 3, spans about 2.2 m
+        const SPACING: f32 = 2.5;
+        const SEED: u64 = 5;
+
+        let mut w = World::new(self.tick_rate, 40.);
+        w.add_force(self.default_force.clone());
+
+        let mut rng = StdRng::seed_from_u64(SEED);
+
+        let columns = (nr_of_bodies as f32).sqrt().ceil().max(1.) as usize;
+        let rows = nr_of_bodies.div_ceil(columns);
+        let width = columns as f32 * SPACING + 2.;
+        let height = rows as f32 * SPACING + 10.;
+
+        w.add_bodies(vec![
+            Body::platform_rectangle_aabb(0.0, -3.0, width, 1.0, material::DEFAULT),
+            Body::platform_rectangle_aabb(
+                -width * 0.5 - 0.05,
+                height * 0.5 - 3.5,
+                0.1,
+                height,
+                material::DEFAULT,
+            ),
+            Body::platform_rectangle_aabb(
+                width * 0.5 + 0.05,
+                height * 0.5 - 3.5,
+                0.1,
+                height,
+                material::DEFAULT,
+            ),
+        ]);
+
+        let bodies: Vec<Body> = (0..nr_of_bodies)
+            .map(|idx| {
+                let x = ((idx % columns) as f32 + 0.5) * SPACING - columns as f32 * SPACING * 0.5;
+                let y = (idx / columns) as f32 * SPACING;
+                random_body(x, y, &mut rng)
+            })
+            .collect();
+
+        w.add_bodies(bodies);
+
+        WorldScene::new((w, WHITE, vec![]))
+            .with_gravity(self.default_gravity)
+            .with_kill_zones(vec![KillZone::Bounds {
+                min: Vector2::new(-width, -50.),
+                max: Vector2::new(width, height + 50.),
+            }])
     }
 
     /// Shows circles of all 5 types of materials bouncing on a platform made of a material of choice.
@@ -134,6 +280,8 @@ impl WorldFactory {
         w.add_bodies(all_circle_materials);
 
         WorldScene::new((w, WHITE, vec![]))
+            .with_gravity(self.default_gravity)
+            .with_kill_zones(play_area())
     }
 
     /// A classic scene regarding physics simulations.<br>
@@ -150,6 +298,8 @@ impl WorldFactory {
         ]);
 
         WorldScene::new((w, WHITE, vec![]))
+            .with_gravity(self.default_gravity)
+            .with_kill_zones(play_area())
     }
 
     /// A simple inclined platform, spawn entities to your liking.
@@ -167,6 +317,8 @@ impl WorldFactory {
         ));
 
         WorldScene::new((w, WHITE, vec![]))
+            .with_gravity(self.default_gravity)
+            .with_kill_zones(play_area())
     }
 
     /// All platform types to observe interactions, spawn entities to your liking.
@@ -181,9 +333,65 @@ impl WorldFactory {
         ]);
 
         WorldScene::new((w, WHITE, vec![]))
+            .with_gravity(self.default_gravity)
+            .with_kill_zones(play_area())
+    }
+}
+
+// emitters
+#[allow(dead_code)]
+impl WorldFactory {
+    /// A fountain of spinning circles in a basin and a cannon firing randomly rotated crates at it.
+    pub fn demo_fountain(&self) -> WorldScene {
+        let mut w = World::new(self.tick_rate, 50.);
+        w.add_force(self.default_force.clone());
+
+        w.add_bodies(vec![
+            Body::platform_rectangle_aabb(0.0, -6.0, 24.0, 0.5, material::DEFAULT),
+            Body::platform_rectangle_aabb(-12.0, -3.0, 0.2, 6.0, material::DEFAULT),
+            Body::platform_rectangle_aabb(12.0, -3.0, 0.2, 6.0, material::DEFAULT),
+        ]);
+
+        let fountain = BodySpawner::new_single_type(
+            Body::circle(0.0, -5.0, 0.15, material::RUBBER),
+            150,
+            20.,
+            0.,
+        )
+        .with_emission(PI * 0.5, 0.25, 8., 11.)
+        .with_spin(-5., 5.)
+        .with_lifetime(15.)
+        .with_seed(3);
+
+        let mut crate_body = Body::obb(-11.0, 2.0, 0.4, 0.4, material::METAL);
+        crate_body.name = Some("crate");
+
+        let cannon = BodySpawner::new_single_type(crate_body, 25, 2., 0.)
+            .with_emission(PI * 0.1, 0.05, 12., 14.)
+            .with_random_rotation()
+            .with_seed(4);
+
+        let mut styles = StyleRegistry::new();
+        styles.insert_name(
+            "crate",
+            BodyStyle::textured("assets/crate.png").with_outline(DARKBROWN, 2.),
+        );
+
+        WorldScene::new((w, WHITE, vec![fountain, cannon]))
+            .with_gravity(self.default_gravity)
+            .with_styles(styles)
+            .with_kill_zones(play_area())
     }
 }
 
+/// Bodies leaving this area fall off the platforms of a demo scene and get removed.
+fn play_area() -> Vec<KillZone> {
+    vec![KillZone::Bounds {
+        min: Vector2::new(-100., -50.),
+        max: Vector2::new(100., 200.),
+    }]
+}
+
 // testing
 #[allow(dead_code, unused_variables)]
 impl WorldFactory {
@@ -201,7 +409,7 @@ impl WorldFactory {
             Body::platform_rectangle_aabb(3.15, -3.0, 0.1, 10.0, material::DEFAULT),
         ]);
 
-        WorldScene::new((w, WHITE, vec![]))
+        WorldScene::new((w, WHITE, vec![])).with_gravity(self.default_gravity)
     }
 
     pub fn test_platform_obb(&self) -> WorldScene {
@@ -214,7 +422,7 @@ impl WorldFactory {
             Body::platform_rectangle_obb(3.15, -3.0, 0.1, 10.0, 0.0, material::DEFAULT),
         ]);
 
-        WorldScene::new((w, WHITE, vec![]))
+        WorldScene::new((w, WHITE, vec![])).with_gravity(self.default_gravity)
     }
 
     pub fn test_local_attractor(&self) -> WorldScene {
@@ -228,22 +436,31 @@ impl WorldFactory {
     pub fn test_polygon(&self) -> WorldScene {
         let mut w = World::new(self.tick_rate, 100.);
 
-        if let Some(poly) = Body::polygon(0.0, 0.0, util::poly_simple(5.0), material::DEFAULT) {
-            w.add_body(poly);
-        };
+        add_polygon(&mut w, 0.0, 0.0, util::poly_simple(5.0), material::DEFAULT);
 
         WorldScene::new((w, WHITE, vec![]))
     }
 
-    // should crash -> no concave polygons allowed
+    /// Concave polygons are not allowed, the polygon is reported and left out of the scene.
     pub fn test_concave_polygon(&self) -> WorldScene {
         let mut w = World::new(self.tick_rate, 100.);
 
-        if let Some(poly) = Body::polygon(0.0, 0.0, util::poly_deep_concave(7.0), material::DEFAULT)
-        {
-            w.add_body(poly);
-        };
+        add_polygon(
+            &mut w,
+            0.0,
+            0.0,
+            util::poly_deep_concave(7.0),
+            material::DEFAULT,
+        );
 
         WorldScene::new((w, WHITE, vec![]))
     }
 }
+
+/// Adds the polygon, if it is valid, otherwise reports why it was left out of the scene.
+fn add_polygon(w: &mut World, x: f32, y: f32, vertices: Vec<Vector2>, material: Material) {
+    match checked_polygon(x, y, vertices, material) {
+        Ok(polygon) => w.add_body(polygon),
+        Err(err) => eprintln!("could not add polygon at ({x}, {y}) to the scene: {err}"),
+    }
+}
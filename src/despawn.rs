@@ -0,0 +1,98 @@
+//! Removes dynamic bodies that leave the play area or exceed their lifetime,
+//! so long running emitter scenes do not grow forever.<br>
+//! Lifetimes are tracked alongside the bodies of the world by their index, since the engine has no stable body ids.
+//! Bodies added via `world.add_body` get no lifetime, removals have to go through the despawner.
+
+use rustycs::{
+    entities::body::{Body, BodyType::*},
+    environment::world::World,
+    math::Vector2,
+};
+
+#[derive(Clone, Copy, Debug)]
+pub enum KillZone {
+    /// removes bodies inside the rectangle
+    Rectangle { min: Vector2, max: Vector2 },
+    /// removes bodies outside of the world bounds
+    Bounds { min: Vector2, max: Vector2 },
+}
+
+impl KillZone {
+    pub fn contains(&self, point: Vector2) -> bool {
+        let inside = |min: Vector2, max: Vector2| {
+            point.x >= min.x && point.x <= max.x && point.y >= min.y && point.y <= max.y
+        };
+
+        match *self {
+            KillZone::Rectangle { min, max } => inside(min, max),
+            KillZone::Bounds { min, max } => !inside(min, max),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Despawner {
+    pub zones: Vec<KillZone>,
+    /// simulation time at which the body with the same index expires
+    expiry: Vec<Option<f32>>,
+}
+
+impl Despawner {
+    pub fn new(zones: Vec<KillZone>) -> Despawner {
+        Despawner {
+            zones,
+            expiry: Vec::new(),
+        }
+    }
+
+    /// Adds a body that gets removed `lifetime` seconds of simulation time after `now`.
+    pub fn add_body(&mut self, world: &mut World, body: Body, lifetime: Option<f32>, now: f32) {
+        self.sync(world);
+        world.add_body(body);
+
+        self.expiry.resize(world.get_bodies().len(), None);
+
+        if let Some(expiry) = self.expiry.last_mut() {
+            *expiry = lifetime.map(|lifetime| now + lifetime);
+        }
+    }
+
+    /// Removes all dynamic bodies within a kill zone or past their lifetime.<br>
+    /// Returns the indices the removed bodies had in ascending order, e.g. for `ContactTracker::remove_bodies`.
+    pub fn apply(&mut self, world: &mut World, now: f32) -> Vec<usize> {
+        self.sync(world);
+
+        let doomed: Vec<usize> = world
+            .get_bodies()
+            .iter()
+            .enumerate()
+            .filter(|(idx, b)| {
+                b.body_type == Dynamic
+                    && (self.expiry[*idx].is_some_and(|expiry| now >= expiry)
+                        || self.zones.iter().any(|z| z.contains(b.transform.location)))
+            })
+            .map(|(idx, _)| idx)
+            .collect();
+
+        // back to front, so the remaining indices stay valid
+        for idx in doomed.iter().rev() {
+            world.remove_body(*idx);
+            self.expiry.remove(*idx);
+        }
+
+        doomed
+    }
+
+    /// Matches the lifetimes to the bodies of the world.<br>
+    /// Appended bodies get no lifetime, if bodies were removed elsewhere the lifetimes can no longer be assigned and are dropped.
+    fn sync(&mut self, world: &World) {
+        let nr_of_bodies = world.get_bodies().len();
+
+        if nr_of_bodies < self.expiry.len() {
+            eprintln!("bodies were removed without the despawner, their lifetimes are reset");
+            self.expiry.clear();
+        }
+
+        self.expiry.resize(nr_of_bodies, None);
+    }
+}
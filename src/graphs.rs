@@ -0,0 +1,142 @@
+//! Time series of simulation metrics, that are shown as live graphs in the HUD.
+
+use std::{collections::VecDeque, time::Instant};
+
+use rustycs::environment::world::World;
+
+use crate::rustycs_utility as util;
+
+/// Upper bound of samples per time series, so the cost of a graph does not depend on the update rate.
+pub const MAX_SAMPLES: usize = 256;
+
+/// Samples of a single metric over the last `window` seconds, at most `MAX_SAMPLES` of them.
+pub struct TimeSeries {
+    pub name: &'static str,
+    pub unit: &'static str,
+    pub samples: VecDeque<(f32, f32)>,
+    pub window: f32,
+}
+
+impl TimeSeries {
+    pub fn new(name: &'static str, unit: &'static str, window: f32) -> TimeSeries {
+        TimeSeries {
+            name,
+            unit,
+            samples: VecDeque::with_capacity(MAX_SAMPLES + 1),
+            window,
+        }
+    }
+
+    /// Samples closer than `window / MAX_SAMPLES` seconds to the previous one are dropped.
+    pub fn push(&mut self, time: f32, value: f32) {
+        if let Some((last, _)) = self.samples.back() {
+            if time - last < self.window / MAX_SAMPLES as f32 {
+                return;
+            }
+        }
+
+        self.samples.push_back((time, value));
+
+        while let Some((t, _)) = self.samples.front() {
+            if time - t <= self.window {
+                break;
+            }
+
+            self.samples.pop_front();
+        }
+    }
+
+    pub fn last(&self) -> Option<f32> {
+        self.samples.back().map(|(_, value)| *value)
+    }
+
+    /// Minimum and maximum of all samples within the window.
+    pub fn range(&self) -> Option<(f32, f32)> {
+        if self.samples.is_empty() {
+            return None;
+        }
+
+        Some(
+            self.samples
+                .iter()
+                .fold((f32::MAX, f32::MIN), |(min, max), (_, v)| {
+                    (min.min(*v), max.max(*v))
+                }),
+        )
+    }
+}
+
+pub struct HudGraphs {
+    pub update_duration: TimeSeries,
+    pub body_count: TimeSeries,
+    pub kinetic_energy: TimeSeries,
+    pub momentum: TimeSeries,
+    pub contact_count: TimeSeries,
+    pub start: Instant,
+}
+
+impl HudGraphs {
+    pub fn new(window_in_secs: f32) -> HudGraphs {
+        HudGraphs {
+            update_duration: TimeSeries::new("update duration", "ms", window_in_secs),
+            body_count: TimeSeries::new("bodies", "", window_in_secs),
+            kinetic_energy: TimeSeries::new("kinetic energy", "J", window_in_secs),
+            momentum: TimeSeries::new("momentum", "Ns", window_in_secs),
+            contact_count: TimeSeries::new("contacts", "", window_in_secs),
+            start: Instant::now(),
+        }
+    }
+
+    /// Records the current state of the world after an update, `update_duration` being its duration in seconds.
+    pub fn record(&mut self, world: &World, update_duration: f32) {
+        let t = self.start.elapsed().as_secs_f32();
+        let bodies = world.get_bodies();
+        let p = util::linear_momentum(bodies);
+
+        self.update_duration.push(t, update_duration * 1000.);
+        self.body_count.push(t, bodies.len() as f32);
+        self.kinetic_energy.push(t, util::kinetic_energy(bodies));
+        self.momentum.push(t, (p.x * p.x + p.y * p.y).sqrt());
+        self.contact_count
+            .push(t, world.collision_points.len() as f32);
+    }
+
+    pub fn all(&self) -> [&TimeSeries; 5] {
+        [
+            &self.update_duration,
+            &self.body_count,
+            &self.kinetic_energy,
+            &self.momentum,
+            &self.contact_count,
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn samples_are_bounded_independent_of_the_rate() {
+        let mut series = TimeSeries::new("test", "", 10.);
+
+        for idx in 0..100_000 {
+            series.push(idx as f32 * 1e-4, idx as f32);
+        }
+
+        assert!(series.samples.len() <= MAX_SAMPLES + 1);
+        assert!(series.samples.len() >= MAX_SAMPLES - 1);
+    }
+
+    #[test]
+    fn samples_older_than_the_window_are_dropped() {
+        let mut series = TimeSeries::new("test", "", 1.);
+
+        for idx in 0..100 {
+            series.push(idx as f32 * 0.1, idx as f32);
+        }
+
+        assert!(series.samples.iter().all(|(t, _)| 9.9 - t <= 1.));
+        assert_eq!(series.last(), Some(99.));
+    }
+}
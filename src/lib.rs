@@ -1,11 +1,39 @@
+pub mod backend;
+pub mod batch;
+pub mod conservation;
+pub mod contacts;
 pub mod controller;
 pub mod demo_scenes;
+pub mod despawn;
+pub mod graphs;
+pub mod metrics;
+pub mod polygon_validation;
+pub mod raster;
 pub mod renderer;
 pub mod rustycs_utility;
 pub mod sim_tracker;
+pub mod spawn_region;
+pub mod stats;
+pub mod styles;
+pub mod svg;
+pub mod templates;
 
+pub use crate::backend::*;
+pub use crate::batch::*;
+pub use crate::conservation::*;
+pub use crate::contacts::*;
 pub use crate::controller::*;
 pub use crate::demo_scenes::*;
+pub use crate::despawn::*;
+pub use crate::graphs::*;
+pub use crate::metrics::*;
+pub use crate::polygon_validation::*;
+pub use crate::raster::*;
 pub use crate::renderer::*;
 pub use crate::rustycs_utility::*;
 pub use crate::sim_tracker::*;
+pub use crate::spawn_region::*;
+pub use crate::stats::*;
+pub use crate::styles::*;
+pub use crate::svg::*;
+pub use crate::templates::*;
@@ -1,4 +1,7 @@
-use std::time::Instant;
+use std::{
+    env, process,
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
 
 use macroquad::{
     prelude::{next_frame, Conf},
@@ -42,6 +45,7 @@ fn window_config() -> Conf {
 const WINDOW_SIZE: (i32, i32) = (1440, 900);
 const TICK_RATE: f32 = 256.;
 const FORCE: Force = GRAVITY_EARTH;
+const GRAPH_WINDOW: f32 = 10.;
 
 #[macroquad::main(window_config)]
 async fn main() {
@@ -51,32 +55,55 @@ async fn main() {
     // choose available scene from factory
     let mut scene = factory.demo_all_platforms();
 
-    let (mut world, bg_color, mut spawners) = scene.extract();
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    let mut metrics: Option<MetricsLog> = take_metrics_arg(&mut args).map(|path| {
+        MetricsLog::create(&path, scene.gravity).unwrap_or_else(|err| {
+            eprintln!("could not create metrics log {path}: {err}");
+            process::exit(1);
+        })
+    });
+    let mut recorded_updates: u32 = 0;
+
+    let (mut world, bg_color, mut spawners, mut styles, mut despawner) = scene.extract();
+    styles.load_textures().await;
     world.set_collision_precision(25);
 
     let mut state: SimulationState = SimulationState::new(world.get_delta_time());
     let mut controller: UserController = UserController::new(10.0, 0.01);
+    let mut contacts: ContactTracker = ContactTracker::default();
+    let mut backend: MacroquadBackend = MacroquadBackend::new();
+    let mut graphs: HudGraphs = HudGraphs::new(GRAPH_WINDOW);
+    let mut conservation: ConservationMonitor =
+        ConservationMonitor::new(scene.gravity, ENERGY_JUMP_THRESHOLD);
 
     let (w, h) = (screen_width(), screen_height());
     let (mut offset_x, mut offset_y): (f32, f32) = (0.0, 0.0);
 
+    let mut frame_instant = Instant::now();
+
     // ------------------------------ SIMULATION LOOP ------------------------------
     loop {
+        state
+            .timings
+            .frame
+            .push(frame_instant.elapsed().as_secs_f32());
+        frame_instant = Instant::now();
+
         if state.update_required() && state.simulation == Running {
+            if state.contact_details == Visible {
+                contacts.record_velocities(&world);
+            }
+
+            let update_start = Instant::now();
             world.update();
+            state
+                .timings
+                .physics
+                .push(update_start.elapsed().as_secs_f32());
             state.nr_of_updates += 1;
             state.tick_instant = Instant::now();
         }
 
-        if state.simulation == Running {
-            for spawner in &mut spawners {
-                if spawner.is_spawnable() {
-                    world.add_body(spawner.spawn());
-                    spawner.timer = Instant::now();
-                }
-            }
-        }
-
         if state.is_pausable() && controller.user_paused() {
             state.simulation.toggle();
             state.pause_instant = Instant::now();
@@ -85,22 +112,133 @@ async fn main() {
         controller.detect_current_actions();
 
         if !controller.active_actions.is_empty() && state.is_spawnable() {
-            controller.handle_current_actions(&mut world, &mut offset_x, &mut offset_y, &mut state);
+            controller.handle_current_actions(
+                &mut world,
+                &mut spawners,
+                &mut contacts,
+                &mut offset_x,
+                &mut offset_y,
+                &mut state,
+            );
         }
 
-        render_world(&world, offset_x, offset_y, &state, bg_color);
+        controller.handle_mouse(&mut world, &mut spawners, (offset_x, offset_y), &mut state);
+
+        // once per world update, including manual updates while paused
+        if state.nr_of_updates != recorded_updates {
+            recorded_updates = state.nr_of_updates;
+            let now = state.simulation_time();
+            let removed = despawner.apply(&mut world, now);
+            state.nr_of_removed += removed.len() as u32;
+            contacts.remove_bodies(&removed);
+
+            let mut nr_of_spawned: usize = 0;
+
+            // spawners run on simulation time, so the spawn sequence does not depend on the frame rate
+            for spawner in &mut spawners {
+                for body in spawner.spawn_due(now) {
+                    despawner.add_body(&mut world, body, spawner.lifetime, now);
+                    nr_of_spawned += 1;
+                }
+            }
+
+            conservation.record(
+                recorded_updates,
+                &world,
+                !removed.is_empty() || nr_of_spawned > 0,
+            );
+
+            if state.graphs == Visible {
+                graphs.record(&world, state.timings.physics.last().unwrap_or(0.));
+            }
+
+            if let Some(log) = &mut metrics {
+                let update_duration = state.timings.physics.last().unwrap_or(0.);
+
+                if let Err(err) = log
+                    .record(recorded_updates, update_duration, &world)
+                    .and_then(|_| log.flush())
+                {
+                    eprintln!("could not write metrics, logging stopped: {err}");
+                    metrics = None;
+                }
+            }
+        }
+
+        if state.contact_details == Visible {
+            contacts.detect(&world);
+        }
+
+        let render_time = render_world(
+            &mut backend,
+            &world,
+            (offset_x, offset_y),
+            &state,
+            &contacts,
+            &styles,
+            bg_color,
+        );
+
+        state.timings.render.push(render_time);
+
+        if state.graphs == Visible {
+            render_graphs(&mut backend, &graphs);
+            backend.flush();
+        }
+
+        if state.conservation == Visible {
+            render_conservation(&mut backend, &conservation);
+        }
+
+        if state.simulation == Paused {
+            render_spawn_tool(
+                &mut backend,
+                &world,
+                (offset_x, offset_y),
+                &controller.spawn_tool,
+            );
+        }
+
+        if state.edit_mode != EditMode::Inactive {
+            render_editor(
+                &mut backend,
+                &world,
+                &spawners,
+                (offset_x, offset_y),
+                &state,
+                &controller.spawner_settings,
+            );
+        }
+
+        if let Some(space) = state.svg_export.take() {
+            let mut svg = SvgBackend::new(w, h, space);
+            render_world(
+                &mut svg,
+                &world,
+                (offset_x, offset_y),
+                &state,
+                &contacts,
+                &styles,
+                bg_color,
+            );
+
+            let millis = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_millis());
+            let path = format!("rustycs_export_{millis}.svg");
+
+            match svg.save(&path) {
+                Ok(()) => println!("exported view to {path}"),
+                Err(err) => eprintln!("could not export view to {path}: {err}"),
+            }
+        }
 
         if state.debug_information == Visible {
             let cam_x = w * 0.5 - offset_x;
             let cam_y = w * 0.5 - offset_y;
             let cam_loc = world.screen_to_world(cam_x, cam_y, w, h);
 
-            render_info_and_benchmark(
-                &mut state,
-                world.get_bodies().len(),
-                world.get_last_update_duration(),
-                cam_loc,
-            );
+            render_info_and_benchmark(&mut backend, &state, world.get_bodies().len(), cam_loc);
         }
 
         next_frame().await
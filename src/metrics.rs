@@ -0,0 +1,97 @@
+//! Per-tick metrics log, so runs of different engine versions can be compared outside of the HUD.<br>
+//! Rows are written as CSV if the file ends in `.csv` and as JSON lines otherwise.<br>
+//! Enabled via `--metrics <path>` in the windowed demo as well as in headless runs.
+
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+};
+
+use rustycs::{environment::world::World, math::Vector2};
+
+use crate::rustycs_utility as util;
+
+pub const METRICS_FLAG: &str = "--metrics";
+
+const CSV_HEADER: &str =
+    "tick,update_duration_ms,bodies,contacts,kinetic_energy,potential_energy,momentum_x,momentum_y";
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MetricsFormat {
+    Csv,
+    JsonLines,
+}
+
+impl MetricsFormat {
+    pub fn from_path(path: &str) -> MetricsFormat {
+        if path.to_lowercase().ends_with(".csv") {
+            MetricsFormat::Csv
+        } else {
+            MetricsFormat::JsonLines
+        }
+    }
+}
+
+pub struct MetricsLog {
+    writer: BufWriter<File>,
+    format: MetricsFormat,
+    /// acceleration of the uniform world force, needed for the potential energy
+    gravity: Vector2,
+}
+
+impl MetricsLog {
+    pub fn create(path: &str, gravity: Vector2) -> io::Result<MetricsLog> {
+        let format = MetricsFormat::from_path(path);
+        let mut writer = BufWriter::new(File::create(path)?);
+
+        if format == MetricsFormat::Csv {
+            writeln!(writer, "{CSV_HEADER}")?;
+        }
+
+        Ok(MetricsLog {
+            writer,
+            format,
+            gravity,
+        })
+    }
+
+    /// Writes one row for the given tick, `update_duration` being the duration of that world update in seconds.
+    pub fn record(&mut self, tick: u32, update_duration: f32, world: &World) -> io::Result<()> {
+        let bodies = world.get_bodies();
+        let kinetic = util::kinetic_energy(bodies);
+        let potential = util::potential_energy(bodies, world.get_attractors(), self.gravity);
+        let p = util::linear_momentum(bodies);
+        let update_ms = update_duration * 1000.;
+        let (nr_of_bodies, contacts) = (bodies.len(), world.collision_points.len());
+
+        match self.format {
+            MetricsFormat::Csv => writeln!(
+                self.writer,
+                "{tick},{update_ms},{nr_of_bodies},{contacts},{kinetic},{potential},{},{}",
+                p.x, p.y
+            ),
+            MetricsFormat::JsonLines => writeln!(
+                self.writer,
+                "{{\"tick\":{tick},\"update_duration_ms\":{update_ms},\"bodies\":{nr_of_bodies},\"contacts\":{contacts},\"kinetic_energy\":{kinetic},\"potential_energy\":{potential},\"momentum_x\":{},\"momentum_y\":{}}}",
+                p.x, p.y
+            ),
+        }
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+/// Removes `--metrics <path>` from the arguments and returns the path, exits if the path is missing.
+pub fn take_metrics_arg(args: &mut Vec<String>) -> Option<String> {
+    let idx = args.iter().position(|arg| arg == METRICS_FLAG)?;
+    args.remove(idx);
+
+    if idx >= args.len() {
+        eprintln!("missing path after {METRICS_FLAG}");
+        std::process::exit(1);
+    }
+
+    Some(args.remove(idx))
+}
@@ -0,0 +1,300 @@
+//! Validation of vertex lists before they are handed to the engine.<br>
+//! `Body::polygon` only returns `None` on invalid input, so this module reports why a polygon is rejected.
+//! The engine expects simple, convex polygons in clockwise order, a counter-clockwise winding can be fixed automatically.
+
+use std::{error::Error, fmt::Display};
+
+use rustycs::{
+    entities::{body::Body, material::Material},
+    math::Vector2,
+};
+
+use crate::rustycs_utility::{self as util, cross};
+
+/// Edges shorter than this are considered degenerate.
+const MIN_EDGE_LENGTH: f32 = 1e-4;
+
+/// Tolerance of cross products, below which corners count as straight.
+const CROSS_EPSILON: f32 = 1e-6;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PolygonError {
+    /// number of vertices
+    TooFewVertices(usize),
+    /// index of the edge, that starts at the vertex with the same index
+    DegenerateEdge(usize),
+    /// all vertices lie on a line
+    ZeroArea,
+    /// indices of the two crossing edges
+    SelfIntersection(usize, usize),
+    /// index of the reflex vertex
+    Concave(usize),
+    CounterClockwise,
+    /// passed all checks, but `Body::polygon` still returned `None`
+    Rejected,
+}
+
+impl Display for PolygonError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use PolygonError::*;
+
+        match self {
+            TooFewVertices(n) => write!(f, "polygons need at least 3 vertices, got {n}"),
+            DegenerateEdge(idx) => write!(f, "edge {idx} is shorter than {MIN_EDGE_LENGTH}"),
+            ZeroArea => write!(f, "all vertices are collinear"),
+            SelfIntersection(a, b) => write!(f, "edges {a} and {b} intersect"),
+            Concave(idx) => write!(
+                f,
+                "vertex {idx} is reflex, only convex polygons are allowed"
+            ),
+            CounterClockwise => write!(f, "vertices are in counter-clockwise order"),
+            Rejected => write!(f, "rejected by the engine"),
+        }
+    }
+}
+
+impl Error for PolygonError {}
+
+/// Checks a vertex list against all requirements of the engine, reporting the first violation found.
+pub fn validate_polygon(vertices: &[Vector2]) -> Result<(), PolygonError> {
+    let n = vertices.len();
+
+    if n < 3 {
+        return Err(PolygonError::TooFewVertices(n));
+    }
+
+    let edge = |idx: usize| (vertices[idx], vertices[(idx + 1) % n]);
+
+    if let Some(idx) = (0..n).find(|&idx| {
+        let (a, b) = edge(idx);
+        ((b.x - a.x).powi(2) + (b.y - a.y).powi(2)).sqrt() < MIN_EDGE_LENGTH
+    }) {
+        return Err(PolygonError::DegenerateEdge(idx));
+    }
+
+    // a zero signed area alone could also be a symmetric self-intersection, e.g. a bow-tie
+    let collinear = (0..n).all(|idx| {
+        let (prev, corner, next) = (
+            vertices[(idx + n - 1) % n],
+            vertices[idx],
+            vertices[(idx + 1) % n],
+        );
+        cross(prev, corner, next).abs() <= CROSS_EPSILON
+    });
+
+    if collinear {
+        return Err(PolygonError::ZeroArea);
+    }
+
+    // non-adjacent edges must not touch, the last edge is adjacent to the first one
+    for a in 0..n {
+        for b in a + 2..n {
+            if a == 0 && b == n - 1 {
+                continue;
+            }
+
+            let ((p1, p2), (q1, q2)) = (edge(a), edge(b));
+
+            if segments_intersect(p1, p2, q1, q2) {
+                return Err(PolygonError::SelfIntersection(a, b));
+            }
+        }
+    }
+
+    let area = util::signed_area(vertices);
+
+    for idx in 0..n {
+        let (prev, corner, next) = (
+            vertices[(idx + n - 1) % n],
+            vertices[idx],
+            vertices[(idx + 1) % n],
+        );
+        let turn = cross(prev, corner, next);
+
+        // adjacent edges folding back onto each other
+        if turn.abs() <= CROSS_EPSILON {
+            let dot = (corner.x - prev.x) * (next.x - corner.x)
+                + (corner.y - prev.y) * (next.y - corner.y);
+
+            if dot < 0. {
+                return Err(PolygonError::SelfIntersection((idx + n - 1) % n, idx));
+            }
+
+            continue;
+        }
+
+        // reflex corners turn against the winding of the polygon
+        if turn.signum() != area.signum() {
+            return Err(PolygonError::Concave(idx));
+        }
+    }
+
+    if area > 0. {
+        return Err(PolygonError::CounterClockwise);
+    }
+
+    Ok(())
+}
+
+/// Reverses counter-clockwise vertices, returns whether the winding was changed.
+pub fn fix_winding(vertices: &mut [Vector2]) -> bool {
+    let ccw = util::signed_area(vertices) > 0.;
+
+    if ccw {
+        vertices.reverse();
+    }
+
+    ccw
+}
+
+/// Fixes the winding and validates the result.
+pub fn prepare_polygon(mut vertices: Vec<Vector2>) -> Result<Vec<Vector2>, PolygonError> {
+    fix_winding(&mut vertices);
+    validate_polygon(&vertices)?;
+
+    Ok(vertices)
+}
+
+/// `Body::polygon` with a reason on invalid input, counter-clockwise vertices are accepted and reversed.
+pub fn checked_polygon(
+    x: f32,
+    y: f32,
+    vertices: Vec<Vector2>,
+    material: Material,
+) -> Result<Body, PolygonError> {
+    let vertices = prepare_polygon(vertices)?;
+
+    Body::polygon(x, y, vertices, material).ok_or(PolygonError::Rejected)
+}
+
+/// Whether the segments share any point, including touching endpoints and collinear overlaps.
+fn segments_intersect(p1: Vector2, p2: Vector2, q1: Vector2, q2: Vector2) -> bool {
+    let (d1, d2) = (cross(q1, q2, p1), cross(q1, q2, p2));
+    let (d3, d4) = (cross(p1, p2, q1), cross(p1, p2, q2));
+
+    let straddles = |a: f32, b: f32| {
+        (a > CROSS_EPSILON && b < -CROSS_EPSILON) || (a < -CROSS_EPSILON && b > CROSS_EPSILON)
+    };
+
+    if straddles(d1, d2) && straddles(d3, d4) {
+        return true;
+    }
+
+    let on_segment = |a: Vector2, b: Vector2, p: Vector2, d: f32| {
+        d.abs() <= CROSS_EPSILON
+            && p.x >= a.x.min(b.x) - CROSS_EPSILON
+            && p.x <= a.x.max(b.x) + CROSS_EPSILON
+            && p.y >= a.y.min(b.y) - CROSS_EPSILON
+            && p.y <= a.y.max(b.y) + CROSS_EPSILON
+    };
+
+    on_segment(q1, q2, p1, d1)
+        || on_segment(q1, q2, p2, d2)
+        || on_segment(p1, p2, q1, d3)
+        || on_segment(p1, p2, q2, d4)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn polygon(points: &[(f32, f32)]) -> Vec<Vector2> {
+        points.iter().map(|&(x, y)| Vector2::new(x, y)).collect()
+    }
+
+    fn clockwise_square() -> Vec<Vector2> {
+        polygon(&[(0., 1.), (1., 1.), (1., 0.), (0., 0.)])
+    }
+
+    #[test]
+    fn clockwise_convex_polygon_is_valid() {
+        assert_eq!(validate_polygon(&clockwise_square()), Ok(()));
+    }
+
+    #[test]
+    fn too_few_vertices() {
+        let line = polygon(&[(0., 0.), (1., 0.)]);
+        assert_eq!(
+            validate_polygon(&line),
+            Err(PolygonError::TooFewVertices(2))
+        );
+    }
+
+    #[test]
+    fn degenerate_edge() {
+        let duplicated = polygon(&[(0., 1.), (1., 1.), (1., 1.), (1., 0.), (0., 0.)]);
+        assert_eq!(
+            validate_polygon(&duplicated),
+            Err(PolygonError::DegenerateEdge(1))
+        );
+    }
+
+    #[test]
+    fn zero_area() {
+        let collinear = polygon(&[(0., 0.), (1., 1.), (2., 2.)]);
+        assert_eq!(validate_polygon(&collinear), Err(PolygonError::ZeroArea));
+    }
+
+    #[test]
+    fn symmetric_bow_tie_is_self_intersecting_not_collinear() {
+        let bow_tie = polygon(&[(0., 0.), (1., 1.), (1., 0.), (0., 1.)]);
+
+        assert!(util::signed_area(&bow_tie).abs() < f32::EPSILON);
+        assert_eq!(
+            validate_polygon(&bow_tie),
+            Err(PolygonError::SelfIntersection(0, 2))
+        );
+    }
+
+    #[test]
+    fn concave() {
+        // clockwise arrow, reflex at the right side of the shaft
+        let arrow = polygon(&[
+            (0., 2.),
+            (1., 1.),
+            (0.3, 1.),
+            (0.3, -1.),
+            (-0.3, -1.),
+            (-0.3, 1.),
+            (-1., 1.),
+        ]);
+
+        assert_eq!(validate_polygon(&arrow), Err(PolygonError::Concave(2)));
+    }
+
+    #[test]
+    fn counter_clockwise() {
+        let mut square = clockwise_square();
+        square.reverse();
+
+        assert_eq!(
+            validate_polygon(&square),
+            Err(PolygonError::CounterClockwise)
+        );
+    }
+
+    #[test]
+    fn counter_clockwise_winding_gets_fixed() {
+        let mut square = clockwise_square();
+        square.reverse();
+
+        assert!(fix_winding(&mut square));
+        assert!(!fix_winding(&mut square));
+        assert_eq!(validate_polygon(&square), Ok(()));
+    }
+
+    #[test]
+    fn prepared_polygons_are_clockwise() {
+        let mut square = clockwise_square();
+        square.reverse();
+
+        let prepared = prepare_polygon(square).unwrap();
+        assert!(util::signed_area(&prepared) < 0.);
+    }
+
+    #[test]
+    fn winding_is_not_fixed_for_invalid_polygons() {
+        let bow_tie = polygon(&[(0., 0.), (1., 1.), (1., 0.), (0., 1.)]);
+        assert!(prepare_polygon(bow_tie).is_err());
+    }
+}
@@ -0,0 +1,199 @@
+//! A CPU rasterizer backend, that draws the same primitives as the window into an image buffer.<br>
+//! It needs neither a display nor a GPU, so scenes can be rendered to PNG sequences on any machine.<br>
+//! Text and textures are not rasterized, textured bodies are drawn as gray placeholders.
+
+use macroquad::{
+    color::Color,
+    math::{vec2, Vec2},
+    texture::{Image, Texture2D},
+};
+
+use crate::backend::DrawBackend;
+
+const PLACEHOLDER_COLOR: Color = Color::new(0.5, 0.5, 0.5, 0.5);
+
+pub struct RasterBackend {
+    width: usize,
+    height: usize,
+    pixels: Vec<Color>,
+}
+
+impl RasterBackend {
+    pub fn new(width: u16, height: u16) -> RasterBackend {
+        RasterBackend {
+            width: width as usize,
+            height: height as usize,
+            pixels: vec![Color::new(0., 0., 0., 1.); width as usize * height as usize],
+        }
+    }
+
+    pub fn to_image(&self) -> Image {
+        let mut bytes = Vec::with_capacity(self.pixels.len() * 4);
+
+        // macroquad images are stored bottom up
+        for row in self.pixels.chunks(self.width).rev() {
+            for pixel in row {
+                let rgba: [u8; 4] = (*pixel).into();
+                bytes.extend_from_slice(&rgba);
+            }
+        }
+
+        Image {
+            bytes,
+            width: self.width as u16,
+            height: self.height as u16,
+        }
+    }
+
+    /// Writes the current buffer as PNG, panics if the file cannot be written.
+    pub fn save_png(&self, path: &str) {
+        self.to_image().export_png(path);
+    }
+}
+
+impl RasterBackend {
+    fn blend(&mut self, x: usize, y: usize, color: Color) {
+        let dst = &mut self.pixels[y * self.width + x];
+        let a = color.a;
+
+        dst.r = color.r * a + dst.r * (1. - a);
+        dst.g = color.g * a + dst.g * (1. - a);
+        dst.b = color.b * a + dst.b * (1. - a);
+        dst.a = a + dst.a * (1. - a);
+    }
+
+    /// Pixel bounds of the given screen area, clipped to the buffer.
+    fn clip(
+        &self,
+        min_x: f32,
+        min_y: f32,
+        max_x: f32,
+        max_y: f32,
+    ) -> Option<(usize, usize, usize, usize)> {
+        if max_x < 0. || max_y < 0. || min_x >= self.width as f32 || min_y >= self.height as f32 {
+            return None;
+        }
+
+        Some((
+            min_x.max(0.).floor() as usize,
+            min_y.max(0.).floor() as usize,
+            max_x.min(self.width as f32 - 1.).ceil() as usize,
+            max_y.min(self.height as f32 - 1.).ceil() as usize,
+        ))
+    }
+
+    /// Fills all pixels whose center lies between the inner and outer radius around (x, y).
+    fn fill_ring(&mut self, x: f32, y: f32, inner: f32, outer: f32, color: Color) {
+        let Some((x0, y0, x1, y1)) = self.clip(x - outer, y - outer, x + outer, y + outer) else {
+            return;
+        };
+
+        let (inner_sq, outer_sq) = (inner * inner, outer * outer);
+
+        for py in y0..=y1 {
+            for px in x0..=x1 {
+                let (dx, dy) = (px as f32 + 0.5 - x, py as f32 + 0.5 - y);
+                let d_sq = dx * dx + dy * dy;
+
+                if d_sq <= outer_sq && d_sq >= inner_sq {
+                    self.blend(px, py, color);
+                }
+            }
+        }
+    }
+}
+
+impl DrawBackend for RasterBackend {
+    fn screen_size(&self) -> (f32, f32) {
+        (self.width as f32, self.height as f32)
+    }
+
+    fn mouse_position(&self) -> (f32, f32) {
+        // no hover information without a mouse
+        (f32::NAN, f32::NAN)
+    }
+
+    fn clear(&mut self, color: Color) {
+        self.pixels.fill(color);
+    }
+
+    fn line(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, thickness: f32, color: Color) {
+        let (dx, dy) = (x2 - x1, y2 - y1);
+        let length = (dx * dx + dy * dy).sqrt();
+
+        if length <= f32::EPSILON {
+            return;
+        }
+
+        // at least one pixel wide, so thin lines do not vanish
+        let half = thickness.max(1.) * 0.5;
+        let (nx, ny) = (-dy / length * half, dx / length * half);
+
+        let (a, b) = (vec2(x1 + nx, y1 + ny), vec2(x2 + nx, y2 + ny));
+        let (c, d) = (vec2(x2 - nx, y2 - ny), vec2(x1 - nx, y1 - ny));
+
+        self.triangle(a, b, c, color);
+        self.triangle(a, c, d, color);
+    }
+
+    fn circle(&mut self, x: f32, y: f32, r: f32, color: Color) {
+        self.fill_ring(x, y, 0., r, color);
+    }
+
+    fn circle_lines(&mut self, x: f32, y: f32, r: f32, thickness: f32, color: Color) {
+        let half = thickness.max(1.) * 0.5;
+        self.fill_ring(x, y, (r - half).max(0.), r + half, color);
+    }
+
+    fn triangle(&mut self, a: Vec2, b: Vec2, c: Vec2, color: Color) {
+        let area = (b.x - a.x) * (c.y - a.y) - (b.y - a.y) * (c.x - a.x);
+
+        if area.abs() <= f32::EPSILON {
+            return;
+        }
+
+        let Some((x0, y0, x1, y1)) = self.clip(
+            a.x.min(b.x).min(c.x),
+            a.y.min(b.y).min(c.y),
+            a.x.max(b.x).max(c.x),
+            a.y.max(b.y).max(c.y),
+        ) else {
+            return;
+        };
+
+        let edge =
+            |p: Vec2, q: Vec2, x: f32, y: f32| (q.x - p.x) * (y - p.y) - (q.y - p.y) * (x - p.x);
+
+        // the top left fill rule is not necessary, the shared edges of the fills are overdrawn by outlines
+        for py in y0..=y1 {
+            for px in x0..=x1 {
+                let (x, y) = (px as f32 + 0.5, py as f32 + 0.5);
+                let (w0, w1, w2) = (edge(b, c, x, y), edge(c, a, x, y), edge(a, b, x, y));
+
+                let inside = if area > 0. {
+                    w0 >= 0. && w1 >= 0. && w2 >= 0.
+                } else {
+                    w0 <= 0. && w1 <= 0. && w2 <= 0.
+                };
+
+                if inside {
+                    self.blend(px, py, color);
+                }
+            }
+        }
+    }
+
+    fn text(&mut self, _text: &str, _x: f32, _y: f32, _font_size: f32, _color: Color) {}
+
+    fn textured_polygon(
+        &mut self,
+        _texture: &Texture2D,
+        points: &[Vec2],
+        _uvs: &[Vec2],
+        triangles: &[[usize; 3]],
+    ) {
+        for [a, b, c] in triangles {
+            self.triangle(points[*a], points[*b], points[*c], PLACEHOLDER_COLOR);
+        }
+    }
+}
@@ -5,24 +5,32 @@ use std::time::Instant;
 
 use macroquad::{
     color::*,
-    prelude::mouse_position,
-    shapes::{draw_circle, draw_circle_lines, draw_line},
-    text::draw_text,
+    math::{vec2, Vec2},
+    texture::Texture2D,
     time::get_fps,
-    window::{clear_background, screen_height, screen_width},
 };
 
 use rustycs::{
     collision::Hitbox,
-    entities::{attractor::AttractorType::*, body::BodyType::*, transform::Transform},
+    entities::{
+        attractor::AttractorType::*,
+        body::{Body, BodyType::*},
+        transform::Transform,
+    },
     environment::world::World,
     math::Vector2,
     shapes::Shape::*,
 };
 
 use crate::{
-    rustycs_utility::Line,
-    sim_tracker::{ShowDebug::*, SimulationMode::*, SimulationState},
+    backend::DrawBackend,
+    conservation::ConservationMonitor,
+    contacts::ContactTracker,
+    controller::{SpawnTool, SpawnerSettings, ToolShape},
+    graphs::{HudGraphs, TimeSeries},
+    rustycs_utility::{self as util, BodySpawner, Line},
+    sim_tracker::{EditMode, FieldOverlay, ShowDebug::*, SimulationMode::*, SimulationState},
+    styles::{BodyStyle, FillMode, StyleRegistry},
 };
 
 const DEBUG_LINE_THICKNESS: f32 = 1.;
@@ -32,25 +40,34 @@ const UI_TEXT_COLOR: Color = BLACK;
 const UI_TEXT_COLOR_PAUSED: Color = WHITE;
 
 pub fn render_world(
+    b: &mut dyn DrawBackend,
     world: &World,
-    offset_x: f32,
-    offset_y: f32,
+    offset: (f32, f32),
     state: &SimulationState,
+    contacts: &ContactTracker,
+    styles: &StyleRegistry,
     bg_color: Color,
 ) -> f32 {
     let render_start = Instant::now();
 
+    let (offset_x, offset_y) = offset;
     let ratio = world.get_ptm_ratio();
-    let w = screen_width();
-    let h = screen_height();
+    let (w, h) = b.screen_size();
 
-    let mp = mouse_position();
+    let mp = b.mouse_position();
     let mouse_hover_pos = world.screen_to_world(mp.0 - offset_x, mp.1 - offset_y, w, h);
 
+    // visible world area including a margin for outlines
+    let margin = BODY_LINE_THICKNESS / ratio;
+    let view_min =
+        world.screen_to_world(-offset_x, h - offset_y, w, h) + Vector2::new(-margin, -margin);
+    let view_max =
+        world.screen_to_world(w - offset_x, -offset_y, w, h) + Vector2::new(margin, margin);
+
     if state.simulation == Paused {
-        clear_background(LIGHTGRAY);
+        b.clear(LIGHTGRAY);
     } else {
-        clear_background(bg_color);
+        b.clear(bg_color);
     }
 
     let mut body_debug_location: Option<(f32, f32)> = None;
@@ -58,80 +75,111 @@ pub fn render_world(
     let mut body_debug_info: Option<&Transform> = None;
 
     if state.debug_grid == Visible {
-        render_grid_f(w, h, ratio);
+        render_grid_f(b, world, w, h, offset_x, offset_y);
+    }
+
+    if state.attractor_field != FieldOverlay::Hidden && !world.get_attractors().is_empty() {
+        render_attractor_field(b, world, w, h, offset_x, offset_y, &state.attractor_field);
     }
 
     world.get_bodies().iter().for_each(|body| {
+        if !is_in_view(body, view_min, view_max) {
+            return;
+        }
+
         // screen position of body.transform.location without camera offset
         let (mut x, mut y) = world.world_to_screen(body.transform.location, w, h);
 
         // absolute screen position of body.transform.location
         (x, y) = (x + offset_x, y + offset_y);
 
-        let material_color = get_material_color(body.material.name);
-
-        if let Some(name) = body.name {
-            let r = body.shape.copy_as_circle().r;
-
-            let color = match name {
-                "mercury" => BROWN,
-                "venus" => WHITE,
-                "earth" => BLUE,
-                "mars" => RED,
-                "jupiter" => ORANGE,
-                "saturn" => GOLD,
-                "uranus" => SKYBLUE,
-                "neptune" => DARKBLUE,
-                _ => BLACK,
+        let mut style = styles
+            .style_of(body)
+            .cloned()
+            .unwrap_or_else(|| get_default_style(body, styles.fill_mode()));
+
+        let sprite = styles
+            .texture_of(&style)
+            .filter(|_| state.sprites == Visible);
+
+        // sprite only styles fall back to the default style in wireframe mode
+        if sprite.is_none() && !style.has_wireframe() {
+            style = BodyStyle {
+                show_label: style.show_label,
+                ..get_default_style(body, styles.fill_mode())
             };
+        }
 
-            draw_circle(x, y, r * ratio, color)
-        } else if body.body_type == Static {
+        if let Some(texture) = sprite {
+            render_sprite(b, body, texture, x, y, ratio);
+        } else {
             match &body.shape {
                 Circle(c) => {
-                    draw_circle_lines(x, y, c.r * ratio, BODY_LINE_THICKNESS, material_color);
-                }
-
-                AABB(_) => {
-                    for line in get_body_outlines(body.get_vertices_as_vec(), ratio, x, y) {
-                        render_line(line, material_color);
+                    if let Some(fill) = style.fill_color {
+                        b.circle(x, y, c.r * ratio, fill);
                     }
-                }
 
-                Polygon(_) => {
-                    for line in get_body_outlines(body.get_vertices_as_vec(), ratio, x, y) {
-                        render_line(line, material_color);
+                    if let Some(outline) = style.outline_color {
+                        b.circle_lines(x, y, c.r * ratio, style.line_thickness, outline);
                     }
-                }
-            }
-        } else {
-            match &body.shape {
-                Circle(c) => {
-                    draw_circle(x, y, c.r * ratio, material_color);
 
-                    let vp = &body.vertices[0];
-                    draw_line(x, y, x + vp.x * ratio, y - vp.y * ratio, 1.0, WHITE)
+                    if body.body_type == Dynamic {
+                        let vp = &body.vertices[0];
+                        let color = style.outline_color.unwrap_or(WHITE);
+                        b.line(x, y, x + vp.x * ratio, y - vp.y * ratio, 1.0, color)
+                    }
                 }
 
-                AABB(_) => {
-                    for line in get_body_outlines(body.get_vertices_as_vec(), ratio, x, y) {
-                        render_line(line, material_color);
+                AABB(_) | Polygon(_) => {
+                    let vertices = &body.vertices[..];
+
+                    if let Some(fill) = style.fill_color {
+                        let to_screen = |v: &Vector2| vec2(x + v.x * ratio, y - v.y * ratio);
+
+                        if is_convex(vertices) {
+                            for idx in 1..vertices.len() - 1 {
+                                b.triangle(
+                                    to_screen(&vertices[0]),
+                                    to_screen(&vertices[idx]),
+                                    to_screen(&vertices[idx + 1]),
+                                    fill,
+                                );
+                            }
+                        } else {
+                            for [i, j, k] in util::triangulate(vertices) {
+                                b.triangle(
+                                    to_screen(&vertices[i]),
+                                    to_screen(&vertices[j]),
+                                    to_screen(&vertices[k]),
+                                    fill,
+                                );
+                            }
+                        }
                     }
-                }
 
-                Polygon(_) => {
-                    for line in get_body_outlines(body.get_vertices_as_vec(), ratio, x, y) {
-                        render_line(line, material_color);
+                    // outline on top of the fill
+                    if let Some(outline) = style.outline_color {
+                        for line in get_outlines(vertices, ratio, x, y) {
+                            render_line(b, line, outline, style.line_thickness);
+                        }
                     }
                 }
             }
         }
 
+        if let (true, Some(name)) = (style.show_label, body.name) {
+            let label_color = style
+                .fill_color
+                .or(style.outline_color)
+                .unwrap_or(UI_TEXT_COLOR);
+
+            b.text(name, x + 5., y - 5., FONT_SIZE, label_color);
+        }
+
         if state.hitboxes == Visible {
             let box_corners = get_hitbox_vertices(&body.hitbox);
-            let lines = get_hitbox_outlines(box_corners, ratio, x, y);
-            for line in lines {
-                render_line(line, BLUE);
+            for line in get_outlines(&box_corners, ratio, x, y) {
+                render_line(b, line, BLUE, BODY_LINE_THICKNESS);
             }
         }
 
@@ -156,69 +204,404 @@ pub fn render_world(
         let (mut x, mut y) = world.world_to_screen(attractor.location, w, h);
         (x, y) = (x + offset_x, y + offset_y);
 
-        if let Some(name) = attractor.name {
-            if name == "sun" {
-                draw_circle(x, y, 2. * ratio, YELLOW);
+        match styles.style_of_attractor(attractor) {
+            Some(style) => {
+                if let Some(fill) = style.fill_color {
+                    b.circle(x, y, 2. * ratio, fill);
+                }
+
+                if let Some(outline) = style.outline_color {
+                    b.circle_lines(x, y, 2. * ratio, style.line_thickness, outline);
+                }
             }
-        } else {
-            draw_circle(x, y, 2.0, BLACK);
+            None => b.circle(x, y, 2.0, BLACK),
         }
 
         if state.hitboxes == Visible && attractor.a_type == Local {
-            draw_circle_lines(x, y, attractor.r * ratio, DEBUG_LINE_THICKNESS, BLUE)
+            b.circle_lines(x, y, attractor.r * ratio, DEBUG_LINE_THICKNESS, BLUE)
         }
     });
 
     if state.collision_points == Visible {
         for p in &world.collision_points {
             let (x, y) = world.world_to_screen(*p, w, h);
-            draw_circle(x + offset_x, y + offset_y, 3., BLUE);
+            b.circle(x + offset_x, y + offset_y, 3., BLUE);
         }
     }
 
+    if state.contact_details == Visible {
+        render_contacts(
+            b,
+            world,
+            contacts,
+            offset_x,
+            offset_y,
+            state.simulation == Paused,
+        );
+    }
+
     // so nothing gets drawn over debug info
     if state.simulation == Paused {
         if let (Some((x, y)), Some(line), Some(info)) =
             (body_debug_location, body_debug_arrow, body_debug_info)
         {
-            render_velocity_pointer(line, WHITE, ratio);
-            render_body_info(x, y, info);
+            render_velocity_pointer(b, line, WHITE, ratio);
+            render_body_info(b, x, y, info);
         }
     }
 
+    b.flush();
+
     render_start.elapsed().as_secs_f32()
 }
 
 // ---------------------- RENDER UTILITY ----------------------
-const GRID_RATIO: f32 = 10.;
+const GRID_MIN_SPACING: f32 = 40.;
+const GRID_COLOR: Color = LIGHTGRAY;
+const GRID_AXIS_COLOR: Color = DARKGRAY;
+const GRID_LABEL_SIZE: f32 = 16.;
+const SCALE_BAR_MARGIN: f32 = 20.;
+
+/// Renders a world aligned grid, where the spacing between lines adapts to the zoom level (1m, 10m, ...).<br>
+/// The world axes are highlighted and labelled, the origin is marked and a scale bar is shown in the bottom left corner.
+fn render_grid_f(
+    b: &mut dyn DrawBackend,
+    world: &World,
+    width: f32,
+    height: f32,
+    offset_x: f32,
+    offset_y: f32,
+) {
+    let ratio = world.get_ptm_ratio();
+    let spacing = get_grid_spacing(ratio);
+
+    // visible world area, taking the camera offset into account
+    let top_left = world.screen_to_world(-offset_x, -offset_y, width, height);
+    let bottom_right = world.screen_to_world(width - offset_x, height - offset_y, width, height);
+
+    let (origin_x, origin_y) = world.world_to_screen(Vector2::new(0., 0.), width, height);
+    let (origin_x, origin_y) = (origin_x + offset_x, origin_y + offset_y);
+
+    // labels stick to the screen border if the respective axis is out of view
+    let label_x = origin_x.clamp(5., width - 60.);
+    let label_y = origin_y.clamp(GRID_LABEL_SIZE, height - 5.);
 
-fn render_grid_f(width: f32, height: f32, _ptm_ratio: f32) {
-    let w_ratio = width / GRID_RATIO;
-    let h_ratio = height / GRID_RATIO;
+    let first_col = (top_left.x / spacing).floor() as i32;
+    let last_col = (bottom_right.x / spacing).ceil() as i32;
 
-    for line_idx in 0..(GRID_RATIO as i32) {
-        let x = w_ratio * line_idx as f32;
-        let y = h_ratio * line_idx as f32;
-        draw_line(x, 0.0, x, height, 1.0, LIGHTGRAY);
-        draw_line(0.0, y, width, y, 1.0, LIGHTGRAY);
+    for col in first_col..=last_col {
+        let x = origin_x + col as f32 * spacing * ratio;
+
+        if col == 0 {
+            b.line(
+                x,
+                0.0,
+                x,
+                height,
+                2. * DEBUG_LINE_THICKNESS,
+                GRID_AXIS_COLOR,
+            );
+            continue;
+        }
+
+        b.line(x, 0.0, x, height, DEBUG_LINE_THICKNESS, GRID_COLOR);
+        b.text(
+            &format_grid_value(col as f32 * spacing, spacing),
+            x + 2.,
+            label_y - 2.,
+            GRID_LABEL_SIZE,
+            GRID_AXIS_COLOR,
+        );
     }
 
-    draw_circle(width * 0.5, height * 0.5, 2., BLACK);
+    let first_row = (bottom_right.y / spacing).floor() as i32;
+    let last_row = (top_left.y / spacing).ceil() as i32;
+
+    for row in first_row..=last_row {
+        let y = origin_y - row as f32 * spacing * ratio;
+
+        if row == 0 {
+            b.line(0.0, y, width, y, 2. * DEBUG_LINE_THICKNESS, GRID_AXIS_COLOR);
+            continue;
+        }
+
+        b.line(0.0, y, width, y, DEBUG_LINE_THICKNESS, GRID_COLOR);
+        b.text(
+            &format_grid_value(row as f32 * spacing, spacing),
+            label_x + 2.,
+            y - 2.,
+            GRID_LABEL_SIZE,
+            GRID_AXIS_COLOR,
+        );
+    }
+
+    b.circle(origin_x, origin_y, 4., GRID_AXIS_COLOR);
+    b.text(
+        "0",
+        origin_x + 4.,
+        origin_y - 4.,
+        GRID_LABEL_SIZE,
+        GRID_AXIS_COLOR,
+    );
+
+    render_scale_bar(b, height, spacing, ratio);
 }
 
-fn render_line(line: Line, color: macroquad::color::Color) {
-    draw_line(
+fn render_scale_bar(b: &mut dyn DrawBackend, height: f32, spacing: f32, ratio: f32) {
+    let bar_length = spacing * ratio;
+    let x = SCALE_BAR_MARGIN;
+    let y = height - SCALE_BAR_MARGIN;
+
+    b.line(
+        x,
+        y,
+        x + bar_length,
+        y,
+        BODY_LINE_THICKNESS,
+        GRID_AXIS_COLOR,
+    );
+    b.line(x, y - 5., x, y + 5., BODY_LINE_THICKNESS, GRID_AXIS_COLOR);
+    b.line(
+        x + bar_length,
+        y - 5.,
+        x + bar_length,
+        y + 5.,
+        BODY_LINE_THICKNESS,
+        GRID_AXIS_COLOR,
+    );
+
+    b.text(
+        &format!(
+            "{} m ({:.4} m/px)",
+            format_grid_value(spacing, spacing),
+            1. / ratio
+        ),
+        x,
+        y - 8.,
+        GRID_LABEL_SIZE,
+        GRID_AXIS_COLOR,
+    );
+}
+
+const FIELD_SAMPLE_SPACING: f32 = 40.;
+const FIELD_COLOR: Color = ORANGE;
+
+/// Samples the pull of all attractors on a screen aligned grid and renders it either as arrows or as a heatmap.<br>
+/// The pull varies by orders of magnitude, so it is scaled logarithmically relative to the strongest visible sample.
+fn render_attractor_field(
+    b: &mut dyn DrawBackend,
+    world: &World,
+    width: f32,
+    height: f32,
+    offset_x: f32,
+    offset_y: f32,
+    overlay: &FieldOverlay,
+) {
+    let attractors = world.get_attractors();
+    let cols = (width / FIELD_SAMPLE_SPACING).ceil() as usize;
+    let rows = (height / FIELD_SAMPLE_SPACING).ceil() as usize;
+
+    let mut samples: Vec<(f32, f32, Vector2, f32)> = Vec::with_capacity(cols * rows);
+    let mut max_intensity: f32 = 0.;
+
+    for row in 0..rows {
+        for col in 0..cols {
+            // center of the sample cell in screen coordinates
+            let x = (col as f32 + 0.5) * FIELD_SAMPLE_SPACING;
+            let y = (row as f32 + 0.5) * FIELD_SAMPLE_SPACING;
+
+            let point = world.screen_to_world(x - offset_x, y - offset_y, width, height);
+            let pull = util::attractor_pull(attractors, point);
+            let intensity = (pull.x * pull.x + pull.y * pull.y).sqrt().ln_1p();
+
+            max_intensity = max_intensity.max(intensity);
+            samples.push((x, y, pull, intensity));
+        }
+    }
+
+    if max_intensity <= 0. {
+        return;
+    }
+
+    for (x, y, pull, intensity) in samples {
+        let relative = intensity / max_intensity;
+
+        match overlay {
+            FieldOverlay::Arrows => {
+                if relative <= 0. {
+                    continue;
+                }
+
+                let magnitude = (pull.x * pull.x + pull.y * pull.y).sqrt();
+                let length = 0.8 * FIELD_SAMPLE_SPACING * relative;
+
+                // screen space has an inverted y-axis
+                let to_x = x + pull.x / magnitude * length;
+                let to_y = y - pull.y / magnitude * length;
+
+                b.line(x, y, to_x, to_y, DEBUG_LINE_THICKNESS, FIELD_COLOR);
+                b.circle(to_x, to_y, 2., FIELD_COLOR);
+            }
+            FieldOverlay::Heatmap => {
+                let half = 0.5 * FIELD_SAMPLE_SPACING;
+                b.rectangle(
+                    x - half,
+                    y - half,
+                    FIELD_SAMPLE_SPACING,
+                    FIELD_SAMPLE_SPACING,
+                    FIELD_COLOR.with_alpha(0.6 * relative),
+                );
+            }
+            FieldOverlay::Hidden => {}
+        }
+    }
+}
+
+const CONTACT_NORMAL_LENGTH: f32 = 30.;
+const CONTACT_NORMAL_COLOR: Color = MAGENTA;
+const CONTACT_DEPTH_COLOR: Color = RED;
+
+/// Renders the normal (fixed length), penetration depth (to scale) and, iff `labelled`,
+/// the involved bodies and the estimated impulse of each contact.
+fn render_contacts(
+    b: &mut dyn DrawBackend,
+    world: &World,
+    tracker: &ContactTracker,
+    offset_x: f32,
+    offset_y: f32,
+    labelled: bool,
+) {
+    let (w, h) = b.screen_size();
+    let ratio = world.get_ptm_ratio();
+
+    for contact in &tracker.contacts {
+        let (mut x, mut y) = world.world_to_screen(contact.point, w, h);
+        (x, y) = (x + offset_x, y + offset_y);
+
+        // screen space has an inverted y-axis
+        let (nx, ny) = (contact.normal.x, -contact.normal.y);
+
+        b.line(
+            x,
+            y,
+            x + nx * CONTACT_NORMAL_LENGTH,
+            y + ny * CONTACT_NORMAL_LENGTH,
+            DEBUG_LINE_THICKNESS,
+            CONTACT_NORMAL_COLOR,
+        );
+
+        b.line(
+            x,
+            y,
+            x - nx * contact.depth * ratio,
+            y - ny * contact.depth * ratio,
+            BODY_LINE_THICKNESS,
+            CONTACT_DEPTH_COLOR,
+        );
+
+        b.circle(x, y, 3., CONTACT_NORMAL_COLOR);
+
+        if labelled {
+            b.text(
+                &format!(
+                    "#{} <-> #{}: depth {:.4} m, est. impulse {:.3} Ns",
+                    contact.body_a, contact.body_b, contact.depth, contact.impulse
+                ),
+                x + 5.,
+                y - 5.,
+                GRID_LABEL_SIZE,
+                UI_TEXT_COLOR_PAUSED,
+            );
+        }
+    }
+}
+
+const SPRITE_CIRCLE_SEGMENTS: usize = 32;
+
+/// Renders the texture onto the body, scaled to its shape.<br>
+/// UV coordinates are mapped in a frame aligned to the first vertex, so the texture rotates with the body.
+fn render_sprite(
+    b: &mut dyn DrawBackend,
+    body: &Body,
+    texture: &Texture2D,
+    x: f32,
+    y: f32,
+    ratio: f32,
+) {
+    let vertices: Vec<Vector2> = match &body.shape {
+        Circle(c) => {
+            let vp = &body.vertices[0];
+            let angle = vp.y.atan2(vp.x);
+
+            (0..SPRITE_CIRCLE_SEGMENTS)
+                .map(|idx| {
+                    let (sin, cos) = (angle
+                        + std::f32::consts::TAU * idx as f32 / SPRITE_CIRCLE_SEGMENTS as f32)
+                        .sin_cos();
+                    Vector2::new(c.r * cos, c.r * sin)
+                })
+                .collect()
+        }
+        AABB(_) | Polygon(_) => body.vertices[..].to_vec(),
+    };
+
+    let angle = vertices[0].y.atan2(vertices[0].x);
+    let (sin, cos) = (-angle).sin_cos();
+
+    let local: Vec<(f32, f32)> = vertices
+        .iter()
+        .map(|v| (v.x * cos - v.y * sin, v.x * sin + v.y * cos))
+        .collect();
+
+    let (min_x, max_x, min_y, max_y) = local.iter().fold(
+        (f32::MAX, f32::MIN, f32::MAX, f32::MIN),
+        |(min_x, max_x, min_y, max_y), (lx, ly)| {
+            (
+                min_x.min(*lx),
+                max_x.max(*lx),
+                min_y.min(*ly),
+                max_y.max(*ly),
+            )
+        },
+    );
+
+    let points: Vec<Vec2> = vertices
+        .iter()
+        .map(|v| vec2(x + v.x * ratio, y - v.y * ratio))
+        .collect();
+
+    let uvs: Vec<Vec2> = local
+        .iter()
+        .map(|(lx, ly)| {
+            vec2(
+                (lx - min_x) / (max_x - min_x),
+                // texture coordinates have an inverted y-axis
+                1. - (ly - min_y) / (max_y - min_y),
+            )
+        })
+        .collect();
+
+    b.textured_polygon(texture, &points, &uvs, &util::triangulate(&vertices));
+}
+
+fn render_line(b: &mut dyn DrawBackend, line: Line, color: Color, thickness: f32) {
+    b.line(
         line.from_x,
         line.from_y,
         line.to_x,
         line.to_y,
-        BODY_LINE_THICKNESS,
+        thickness,
         color,
     );
 }
 
-fn render_velocity_pointer(arrow_line: Line, color: macroquad::color::Color, ratio: f32) {
-    draw_line(
+fn render_velocity_pointer(
+    b: &mut dyn DrawBackend,
+    arrow_line: Line,
+    color: macroquad::color::Color,
+    ratio: f32,
+) {
+    b.line(
         arrow_line.from_x,
         arrow_line.from_y,
         arrow_line.to_x,
@@ -227,10 +610,10 @@ fn render_velocity_pointer(arrow_line: Line, color: macroquad::color::Color, rat
         color,
     );
 
-    draw_circle(arrow_line.to_x, arrow_line.to_y, 0.01 * ratio, color);
+    b.circle(arrow_line.to_x, arrow_line.to_y, 0.01 * ratio, color);
 }
 
-fn render_body_info(x: f32, y: f32, transform: &Transform) {
+fn render_body_info(b: &mut dyn DrawBackend, x: f32, y: f32, transform: &Transform) {
     let infos: [&str; 3] = [
         &format!("location: {location}", location = transform.location),
         &format!("velocity: {velocity}", velocity = transform.velocity),
@@ -241,7 +624,7 @@ fn render_body_info(x: f32, y: f32, transform: &Transform) {
     ];
 
     for (idx, info) in infos.iter().enumerate() {
-        draw_text(
+        b.text(
             info,
             x + 50.,
             y - 20. + (20. * idx as f32),
@@ -252,6 +635,28 @@ fn render_body_info(x: f32, y: f32, transform: &Transform) {
 }
 
 // ---------------------- RENDER GETTERS ----------------------
+/// Style of bodies without a registered style, based on the material color and the fill mode of the scene.
+fn get_default_style(body: &Body, fill_mode: FillMode) -> BodyStyle {
+    let material_color = get_material_color(body.material.name);
+
+    match fill_mode {
+        FillMode::Filled => BodyStyle::filled(material_color),
+        FillMode::Outline => BodyStyle::outlined(material_color),
+        FillMode::Both => BodyStyle::filled(material_color.with_alpha(0.5))
+            .with_outline(material_color, BODY_LINE_THICKNESS),
+    }
+}
+
+/// Smallest power of ten (in meters), so that grid lines are at least `GRID_MIN_SPACING` pixels apart.
+fn get_grid_spacing(ptm_ratio: f32) -> f32 {
+    10_f32.powf((GRID_MIN_SPACING / ptm_ratio).log10().ceil())
+}
+
+fn format_grid_value(value: f32, spacing: f32) -> String {
+    let decimals = (-spacing.log10().round()).max(0.) as usize;
+    format!("{value:.decimals$}")
+}
+
 fn get_material_color(material_type: &'static str) -> Color {
     match material_type {
         // also black to properly see objects
@@ -264,16 +669,20 @@ fn get_material_color(material_type: &'static str) -> Color {
     }
 }
 
-fn get_body_outlines(vertices: Vec<Vector2>, ratio: f32, x: f32, y: f32) -> Vec<Line> {
+/// Outlines of a closed shape in screen coordinates, without allocating.
+fn get_outlines(
+    vertices: &[Vector2],
+    ratio: f32,
+    x: f32,
+    y: f32,
+) -> impl Iterator<Item = Line> + '_ {
     let nr_vertices = vertices.len();
 
     if nr_vertices < 3 {
         panic!("Cannot generate lines for shapes with less than 3 vertices.")
     }
 
-    let mut lines: Vec<Line> = Vec::new();
-
-    for idx in 0..nr_vertices {
+    (0..nr_vertices).map(move |idx| {
         let from = &vertices[idx];
         let to = &vertices[(idx + 1) % nr_vertices];
 
@@ -282,14 +691,12 @@ fn get_body_outlines(vertices: Vec<Vector2>, ratio: f32, x: f32, y: f32) -> Vec<
         line *= ratio;
         line.apply_screen_location(x, y);
 
-        lines.push(line)
-    }
-
-    lines
+        line
+    })
 }
 
-fn get_hitbox_vertices(hitbox: &Hitbox) -> Vec<Vector2> {
-    vec![
+fn get_hitbox_vertices(hitbox: &Hitbox) -> [Vector2; 4] {
+    [
         Vector2::new(hitbox.min.x, hitbox.max.y),
         Vector2::new(hitbox.max.x, hitbox.max.y),
         Vector2::new(hitbox.max.x, hitbox.min.y),
@@ -297,59 +704,95 @@ fn get_hitbox_vertices(hitbox: &Hitbox) -> Vec<Vector2> {
     ]
 }
 
-fn get_hitbox_outlines(vertices: Vec<Vector2>, ratio: f32, x: f32, y: f32) -> Vec<Line> {
-    let mut lines: Vec<Line> = Vec::new();
+/// Checks the hitbox of the body (relative to its location) against the visible world area.
+fn is_in_view(body: &Body, view_min: Vector2, view_max: Vector2) -> bool {
+    let loc = body.transform.location;
 
-    for idx in 0..4 {
-        let from = &vertices[idx];
-        let to = &vertices[(idx + 1) % 4];
+    loc.x + body.hitbox.max.x >= view_min.x
+        && loc.x + body.hitbox.min.x <= view_max.x
+        && loc.y + body.hitbox.max.y >= view_min.y
+        && loc.y + body.hitbox.min.y <= view_max.y
+}
 
-        let mut line = Line::new(from.x, from.y, to.x, to.y);
+fn is_convex(vertices: &[Vector2]) -> bool {
+    let n = vertices.len();
+    let mut sign = 0.;
 
-        line *= ratio;
-        line.apply_screen_location(x, y);
+    for idx in 0..n {
+        let (a, b, c) = (
+            vertices[idx],
+            vertices[(idx + 1) % n],
+            vertices[(idx + 2) % n],
+        );
+        let turn = (b.x - a.x) * (c.y - b.y) - (b.y - a.y) * (c.x - b.x);
+
+        if turn == 0. {
+            continue;
+        }
 
-        lines.push(line)
+        if sign == 0. {
+            sign = turn.signum();
+        } else if turn.signum() != sign {
+            return false;
+        }
     }
 
-    lines
+    true
 }
 
 // ---------------------- INFO ----------------------
 const PAUSE_MENU_INFO: &str = "Press [ESC] to pause the simulation and show options.";
 
-const MANUAL: [&str; 8] = [
-    "[1] Circle; [2] AABB; [3] OBB; [4] Polygon; [5] Attractor",
+const MANUAL: [&str; 12] = [
+    "[1] Circle; [2] AABB; [3] OBB; [4] Polygon, selected and placed with the palette settings; [5] Attractor",
     "[W][A][S][D] move camera",
     "[UP][DOWN] zoom camera in/out",
     "[R] reset camera to center",
-    "[T] toggle text; [H] toggle hitboxes",
-    "[C] toggle collision points; [G] toggle grid",
+    "[T] toggle text; [H] toggle hitboxes; [X] toggle sprites/wireframe",
+    "[C] toggle collision points; [K] toggle contact details; [G] toggle grid",
+    "[F] cycle attractor field (arrows/heatmap/off); [M] toggle graphs",
     "[HOVER BODY] when paused, for body information",
-    "[U] when paused, to update world manually",
+    "[U] when paused, to update world manually; [B] reset timing statistics",
+    "[E] toggle energy and momentum conservation monitor; [N] pause/resume spawners",
+    "[P] export view as SVG; [O] export view as SVG in world coordinates",
+    "[Q] cycle edit mode (spawners/attractors/off); [CLICK] place/select, [DRAG] move",
 ];
 
 pub fn render_info_and_benchmark(
-    state: &mut SimulationState,
+    b: &mut dyn DrawBackend,
+    state: &SimulationState,
     nr_of_bodies: usize,
-    update_time: f32,
     camera_pos: Vector2,
 ) {
-    // show_fps();
+    // show_fps(b);
+
+    let mut benchmark_info: Vec<String> = vec![
+        format!("updates: {}", state.nr_of_updates),
+        format!("entity count: {}", nr_of_bodies),
+        format!("removed bodies: {}", state.nr_of_removed),
+        format!("camera location: {}", camera_pos),
+        format!(
+            "timings in ms over the last {} samples ([B] to reset):",
+            state.timings.physics.window
+        ),
+    ];
 
-    if state.max_update_duration < update_time {
-        state.max_update_duration = update_time;
+    for (name, stats) in state.timings.all() {
+        benchmark_info.push(match stats.summary() {
+            Some(s) => format!(
+                "{name:>8}: mean {:.2} | median {:.2} | p95 {:.2} | p99 {:.2} | max {:.2}",
+                s.mean * 1000.,
+                s.median * 1000.,
+                s.p95 * 1000.,
+                s.p99 * 1000.,
+                s.max * 1000.
+            ),
+            None => format!("{name:>8}: no samples"),
+        });
     }
 
-    let benchmark_info: [&str; 4] = [
-        &format!("updates: {}", state.nr_of_updates),
-        &format!("max update duration: {:.2}", state.max_update_duration),
-        &format!("entity count: {}", nr_of_bodies),
-        &format!("camera location: {}", camera_pos),
-    ];
-
     for (idx, info) in benchmark_info.iter().enumerate() {
-        draw_text(
+        b.text(
             info,
             20.,
             30. + (20. * idx as f32),
@@ -358,14 +801,14 @@ pub fn render_info_and_benchmark(
         );
     }
 
-    let inst_pos = screen_width() - 600.;
+    let inst_pos = b.screen_size().0 - 600.;
 
     // PAUSE AND USAGE
-    draw_text(PAUSE_MENU_INFO, inst_pos, 30.0, FONT_SIZE, UI_TEXT_COLOR);
+    b.text(PAUSE_MENU_INFO, inst_pos, 30.0, FONT_SIZE, UI_TEXT_COLOR);
 
     if state.simulation == Paused {
         for (idx, instruction) in MANUAL.iter().enumerate() {
-            draw_text(
+            b.text(
                 instruction,
                 inst_pos,
                 50. + (20. * idx as f32),
@@ -376,7 +819,435 @@ pub fn render_info_and_benchmark(
     }
 }
 
+const GHOST_COLOR: Color = Color::new(0., 0., 0., 0.4);
+
+/// Renders the body of the spawn tool under the cursor and its settings below the manual.
+pub fn render_spawn_tool(
+    b: &mut dyn DrawBackend,
+    world: &World,
+    offset: (f32, f32),
+    tool: &SpawnTool,
+) {
+    let (w, h) = b.screen_size();
+    let ratio = world.get_ptm_ratio();
+    let (x, y) = b.mouse_position();
+    let location = world.screen_to_world(x - offset.0, y - offset.1, w, h);
+
+    if tool.shape == ToolShape::Circle {
+        let r = 0.5 * tool.width * ratio;
+        b.circle_lines(x, y, r, BODY_LINE_THICKNESS, GHOST_COLOR);
+
+        // same orientation line as the placed circle, drawn from its rotated reference vertex
+        if let Some(vp) = tool
+            .build(location)
+            .ok()
+            .and_then(|c| c.vertices.first().copied())
+        {
+            b.line(
+                x,
+                y,
+                x + vp.x * ratio,
+                y - vp.y * ratio,
+                DEBUG_LINE_THICKNESS,
+                GHOST_COLOR,
+            );
+        }
+    } else {
+        for line in get_outlines(&tool.outline(), ratio, x, y) {
+            render_line(b, line, GHOST_COLOR, BODY_LINE_THICKNESS);
+        }
+    }
+
+    let palette: [String; 3] = [
+        format!(
+            "spawn tool: [TAB] {} | [LEFT/RIGHT] width {:.2} m | [,/.] height {:.2} m",
+            tool.shape.name(),
+            tool.width,
+            tool.height
+        ),
+        format!(
+            "[Y] material {} | [[/]] rotation {:.0} deg | [SPACE] place at {}",
+            tool.material_name(),
+            tool.rotation.to_degrees(),
+            location
+        ),
+        String::from("[1]-[4] select a shape and place it with these settings right away"),
+    ];
+
+    let y = 70. + 20. * MANUAL.len() as f32;
+
+    for (idx, line) in palette.iter().enumerate() {
+        b.text(
+            line,
+            w - 600.,
+            y + 20. * idx as f32,
+            FONT_SIZE,
+            UI_TEXT_COLOR_PAUSED,
+        );
+    }
+}
+
+const CONSERVATION_INFO_Y: f32 = 190.;
+const CONSERVATION_WARNING_COLOR: Color = RED;
+
+/// Renders the current energies, their drift since the baseline and all flagged energy jumps.
+pub fn render_conservation(b: &mut dyn DrawBackend, monitor: &ConservationMonitor) {
+    let (Some(now), Some(base), Some(drift)) = (monitor.current, monitor.baseline, monitor.drift())
+    else {
+        return;
+    };
+
+    let relative = drift
+        .relative_energy
+        .map_or(String::from("-"), |r| format!("{:+.2} %", r * 100.));
+
+    let info: [String; 3] = [
+        format!(
+            "energy: kinetic {:.2} J | potential {:.2} J | total {:.2} J",
+            now.kinetic,
+            now.potential,
+            now.total_energy()
+        ),
+        format!(
+            "drift since tick {}: energy {:+.2} J ({relative}) | momentum {:.3} Ns | angular momentum {:+.3} Nms",
+            base.tick, drift.energy, drift.momentum, drift.angular_momentum
+        ),
+        format!(
+            "energy jumps > {:.0} %: {} {}",
+            monitor.threshold * 100.,
+            monitor.nr_of_jumps,
+            monitor
+                .flagged_ticks
+                .iter()
+                .map(|tick| tick.to_string())
+                .collect::<Vec<String>>()
+                .join(", ")
+        ),
+    ];
+
+    for (idx, line) in info.iter().enumerate() {
+        let color = if idx == 2 && monitor.nr_of_jumps > 0 {
+            CONSERVATION_WARNING_COLOR
+        } else {
+            UI_TEXT_COLOR
+        };
+
+        b.text(
+            line,
+            20.,
+            CONSERVATION_INFO_Y + 20. * idx as f32,
+            FONT_SIZE,
+            color,
+        );
+    }
+}
+
+const SPAWNER_ICON_SIZE: f32 = 10.;
+const SPAWNER_COLOR: Color = DARKGREEN;
+const SPAWNER_SELECTED_COLOR: Color = ORANGE;
+const SPAWNER_PAUSED_COLOR: Color = GRAY;
+const ATTRACTOR_PREVIEW_COLOR: Color = PURPLE;
+
+/// Renders all spawners as icons, the radius of an attractor being dragged out and the editor settings.
+pub fn render_editor(
+    b: &mut dyn DrawBackend,
+    world: &World,
+    spawners: &[BodySpawner],
+    offset: (f32, f32),
+    state: &SimulationState,
+    settings: &SpawnerSettings,
+) {
+    let (w, h) = b.screen_size();
+    let to_screen = |location: Vector2| {
+        let (x, y) = world.world_to_screen(location, w, h);
+        (x + offset.0, y + offset.1)
+    };
+
+    for (idx, spawner) in spawners.iter().enumerate() {
+        let (x, y) = to_screen(spawner.location());
+
+        let color = if state.selected_spawner == Some(idx) {
+            SPAWNER_SELECTED_COLOR
+        } else if spawner.paused {
+            SPAWNER_PAUSED_COLOR
+        } else {
+            SPAWNER_COLOR
+        };
+
+        let half = SPAWNER_ICON_SIZE;
+        b.line(
+            x - half,
+            y - half,
+            x + half,
+            y - half,
+            DEBUG_LINE_THICKNESS,
+            color,
+        );
+        b.line(
+            x + half,
+            y - half,
+            x + half,
+            y + half,
+            DEBUG_LINE_THICKNESS,
+            color,
+        );
+        b.line(
+            x + half,
+            y + half,
+            x - half,
+            y + half,
+            DEBUG_LINE_THICKNESS,
+            color,
+        );
+        b.line(
+            x - half,
+            y + half,
+            x - half,
+            y - half,
+            DEBUG_LINE_THICKNESS,
+            color,
+        );
+
+        // screen space has an inverted y-axis
+        if let Some(cone) = &spawner.emission {
+            let length = 2.5 * SPAWNER_ICON_SIZE;
+            let (to_x, to_y) = (
+                x + cone.direction.cos() * length,
+                y - cone.direction.sin() * length,
+            );
+            b.line(x, y, to_x, to_y, DEBUG_LINE_THICKNESS, color);
+        }
+
+        let remaining = spawner
+            .remaining()
+            .map_or(String::from("inf"), |n| n.to_string());
+        b.text(
+            &format!("#{idx} ({remaining})"),
+            x + half + 4.,
+            y - half,
+            FONT_SIZE,
+            color,
+        );
+    }
+
+    if state.edit_mode == EditMode::Attractors {
+        if let Some(center) = state.drag_start {
+            let (x, y) = to_screen(center);
+            let (mx, my) = b.mouse_position();
+            let r = ((mx - x).powi(2) + (my - y).powi(2)).sqrt();
+
+            b.circle_lines(x, y, r, DEBUG_LINE_THICKNESS, ATTRACTOR_PREVIEW_COLOR);
+            b.circle(x, y, 3., ATTRACTOR_PREVIEW_COLOR);
+        }
+    }
+
+    let info = match state.edit_mode {
+        EditMode::Inactive => return,
+        EditMode::Spawners => format!(
+            "[Q] edit mode: spawners | [Z] preset: {} | [-/=] frequency: {} Hz | [V] amount: {} | [DEL] delete selected",
+            settings.preset.name(),
+            settings.frequency_in_hz,
+            settings.amount().map_or(String::from("unlimited"), |n| n.to_string())
+        ),
+        EditMode::Attractors => String::from(
+            "[Q] edit mode: attractors | [DRAG] from the center to set the radius of a local attractor",
+        ),
+    };
+
+    b.text(&info, 20., h - 20., FONT_SIZE, UI_TEXT_COLOR);
+}
+
+const GRAPH_WIDTH: f32 = 240.;
+const GRAPH_HEIGHT: f32 = 40.;
+const GRAPH_SPACING: f32 = 24.;
+const GRAPH_MARGIN: f32 = 20.;
+const GRAPH_COLOR: Color = DARKBLUE;
+const GRAPH_BG_COLOR: Color = Color::new(1., 1., 1., 0.75);
+
+/// Renders all time series as small line graphs in the bottom right corner.
+pub fn render_graphs(b: &mut dyn DrawBackend, graphs: &HudGraphs) {
+    let (w, h) = b.screen_size();
+    let series = graphs.all();
+
+    let panel_height = series.len() as f32 * (GRAPH_HEIGHT + GRAPH_SPACING);
+    let x = w - GRAPH_WIDTH - GRAPH_MARGIN;
+    let mut y = h - panel_height - GRAPH_MARGIN;
+
+    b.rectangle(
+        x - 10.,
+        y - 10.,
+        GRAPH_WIDTH + 20.,
+        panel_height + 10.,
+        GRAPH_BG_COLOR,
+    );
+
+    for ts in series {
+        render_time_series(b, ts, x, y + GRAPH_SPACING);
+        y += GRAPH_HEIGHT + GRAPH_SPACING;
+    }
+}
+
+fn render_time_series(b: &mut dyn DrawBackend, ts: &TimeSeries, x: f32, y: f32) {
+    let (Some(last), Some((min, max))) = (ts.last(), ts.range()) else {
+        return;
+    };
+
+    b.text(
+        &format!(
+            "{}: {last:.2} {unit} [{min:.2}, {max:.2}]",
+            ts.name,
+            unit = ts.unit
+        ),
+        x,
+        y - 6.,
+        GRID_LABEL_SIZE,
+        UI_TEXT_COLOR,
+    );
+
+    b.line(
+        x,
+        y + GRAPH_HEIGHT,
+        x + GRAPH_WIDTH,
+        y + GRAPH_HEIGHT,
+        DEBUG_LINE_THICKNESS,
+        GRAY,
+    );
+
+    let t_end = ts.samples.back().map_or(0., |(t, _)| *t);
+    let t_start = t_end - ts.window;
+    let span = if max > min { max - min } else { 1. };
+
+    let to_screen = |(t, v): &(f32, f32)| {
+        (
+            x + (t - t_start) / ts.window * GRAPH_WIDTH,
+            y + GRAPH_HEIGHT - (v - min) / span * GRAPH_HEIGHT,
+        )
+    };
+
+    for (from, to) in ts.samples.iter().zip(ts.samples.iter().skip(1)) {
+        let (x1, y1) = to_screen(from);
+        let (x2, y2) = to_screen(to);
+        b.line(x1, y1, x2, y2, DEBUG_LINE_THICKNESS, GRAPH_COLOR);
+    }
+}
+
 #[allow(dead_code)]
-fn show_fps() {
-    draw_text(&get_fps().to_string(), 10., 10., 20., BLACK);
+fn show_fps(b: &mut dyn DrawBackend) {
+    b.text(&get_fps().to_string(), 10., 10., 20., BLACK);
+}
+
+#[cfg(test)]
+mod tests {
+    use rustycs::entities::material;
+
+    use super::*;
+    use crate::backend::{DrawCommand, RecordingBackend};
+
+    const WIDTH: f32 = 800.;
+    const HEIGHT: f32 = 600.;
+    const PTM_RATIO: f32 = 100.;
+
+    fn world_with_circle(x: f32, y: f32, r: f32) -> World {
+        let mut world = World::new(60., PTM_RATIO);
+        world.add_body(Body::circle(x, y, r, material::RUBBER));
+        world
+    }
+
+    fn render(world: &World, state: &SimulationState) -> RecordingBackend {
+        let mut backend = RecordingBackend::new(WIDTH, HEIGHT);
+
+        render_world(
+            &mut backend,
+            world,
+            (0., 0.),
+            state,
+            &ContactTracker::default(),
+            &StyleRegistry::new(),
+            WHITE,
+        );
+
+        backend
+    }
+
+    #[test]
+    fn world_is_cleared_with_the_background_color_first() {
+        let backend = render(&world_with_circle(0., 0., 0.5), &SimulationState::default());
+
+        assert_eq!(backend.commands.first(), Some(&DrawCommand::Clear(WHITE)));
+    }
+
+    #[test]
+    fn paused_world_is_cleared_gray() {
+        let state = SimulationState {
+            simulation: Paused,
+            ..Default::default()
+        };
+        let backend = render(&world_with_circle(0., 0., 0.5), &state);
+
+        assert_eq!(
+            backend.commands.first(),
+            Some(&DrawCommand::Clear(LIGHTGRAY))
+        );
+    }
+
+    #[test]
+    fn visible_circle_is_filled_and_outlined_in_its_material_color() {
+        let backend = render(&world_with_circle(0., 0., 0.5), &SimulationState::default());
+        let r = 0.5 * PTM_RATIO;
+
+        assert!(backend.commands.iter().any(|command| matches!(
+            command,
+            DrawCommand::Circle { r: cr, color, .. } if *cr == r && *color == RED.with_alpha(0.5)
+        )));
+        assert!(backend.commands.iter().any(|command| matches!(
+            command,
+            DrawCommand::CircleLines { r: cr, color, .. } if *cr == r && *color == RED
+        )));
+    }
+
+    #[test]
+    fn bodies_outside_of_the_view_are_culled() {
+        let backend = render(
+            &world_with_circle(1000., 1000., 0.5),
+            &SimulationState::default(),
+        );
+
+        assert!(!backend.commands.iter().any(|command| matches!(
+            command,
+            DrawCommand::Circle { .. } | DrawCommand::CircleLines { .. }
+        )));
+    }
+
+    #[test]
+    fn info_shows_update_and_entity_count() {
+        let mut backend = RecordingBackend::new(WIDTH, HEIGHT);
+        let state = SimulationState {
+            nr_of_updates: 3,
+            ..Default::default()
+        };
+
+        render_info_and_benchmark(&mut backend, &state, 5, Vector2::new(0., 0.));
+
+        let texts: Vec<&str> = backend.texts().collect();
+
+        assert!(texts.contains(&"updates: 3"));
+        assert!(texts.contains(&"entity count: 5"));
+        assert!(texts.contains(&PAUSE_MENU_INFO));
+        assert!(!texts.contains(&MANUAL[0]));
+    }
+
+    #[test]
+    fn info_shows_manual_when_paused() {
+        let mut backend = RecordingBackend::new(WIDTH, HEIGHT);
+        let state = SimulationState {
+            simulation: Paused,
+            ..Default::default()
+        };
+
+        render_info_and_benchmark(&mut backend, &state, 0, Vector2::new(0., 0.));
+
+        let texts: Vec<&str> = backend.texts().collect();
+
+        assert!(MANUAL.iter().all(|line| texts.contains(line)));
+    }
 }
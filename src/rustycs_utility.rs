@@ -2,14 +2,21 @@
 //! Mainly content that is necessary for ease of use e.g. within scenes, but has no place in the engine itself.<br><br>
 //! For example, it provides functionality such as:
 //! - a line struct to enable easier line rendering
-//! - predefined polygon shapes
+//! - predefined and generated polygon shapes
+//! - polygon triangulation
 //! - random body functionality
 //! - a body spawner
+//! - attractor field sampling
+//! - energy and momentum measurements
 //! - demo scene specific definitions (e.g. solar system)
 
 #![allow(unused_variables, dead_code)]
-use rand::{rngs::ThreadRng, thread_rng, Rng};
-use std::{fmt::Display, ops, time::Instant};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::{
+    f32::consts::{FRAC_PI_2, PI, TAU},
+    fmt::Display,
+    ops,
+};
 
 use rustycs::{
     entities::{
@@ -21,6 +28,12 @@ use rustycs::{
     shapes::{Circle, Polygon, Shape, AABB},
 };
 
+use crate::{
+    polygon_validation::{checked_polygon, PolygonError},
+    spawn_region::{RegionSampler, SpawnRegion, SpawnSampling},
+    templates::{pick_weighted, BodyTemplate, TemplateShape},
+};
+
 // ------------------- Lines -------------------
 #[derive(Clone, Debug)]
 pub struct Line {
@@ -68,13 +81,43 @@ impl ops::MulAssign<f32> for Line {
 }
 
 // ------------------- Random Shape -------------------
-pub fn random_body(x: f32, y: f32, rng: &mut ThreadRng) -> Body {
+pub fn random_body(x: f32, y: f32, rng: &mut impl Rng) -> Body {
     Body::new(x, y, random_shape(rng), Dynamic, random_material(rng), None)
 }
 
-fn random_shape(rng: &mut ThreadRng) -> Shape {
-    let mut rng = thread_rng();
+/// Templates with the same distribution of shapes, sizes and materials as `random_body`.
+pub fn random_body_templates() -> Vec<BodyTemplate> {
+    let shapes = [
+        (TemplateShape::Circle, 0.1, 0.5, 2.),
+        (TemplateShape::Box, 0.2, 1.0, 2.),
+        (TemplateShape::Polygon(poly_simple), 1.0, 3.0, 1.),
+        (TemplateShape::Polygon(poly_triangle), 1.0, 3.0, 1.),
+    ];
 
+    let materials = [
+        material::RUBBER,
+        material::PLASTIC,
+        material::STONE,
+        material::METAL,
+    ];
+
+    shapes
+        .iter()
+        .flat_map(|(shape, min_size, max_size, weight)| {
+            materials.iter().map(move |material| {
+                BodyTemplate::new(
+                    shape.clone(),
+                    *min_size,
+                    *max_size,
+                    material.clone(),
+                    *weight,
+                )
+            })
+        })
+        .collect()
+}
+
+fn random_shape(rng: &mut impl Rng) -> Shape {
     let shape: u8 = rng.gen_range(0..3);
 
     match shape {
@@ -101,7 +144,7 @@ fn random_shape(rng: &mut ThreadRng) -> Shape {
     }
 }
 
-fn random_material(rng: &mut ThreadRng) -> material::Material {
+fn random_material(rng: &mut impl Rng) -> material::Material {
     let material: u8 = rng.gen_range(0..4);
 
     match material {
@@ -142,6 +185,324 @@ pub fn poly_complex(scale: f32) -> Vec<Vector2> {
     ]
 }
 
+// ------------------- Polygon shape generators -------------------
+// ALL GENERATORS RETURN CLOCKWISE ORDER, CENTERED AROUND THE ORIGIN
+// SIZES ARE TAKEN BY THEIR MAGNITUDE, VERTEX AND SEGMENT COUNTS ARE RAISED TO THEIR MINIMUM
+
+const MIN_VERTEX_DISTANCE: f32 = 1e-4;
+const RANDOM_HULL_ATTEMPTS: usize = 8;
+
+/// Regular polygon with `n` (at least 3) vertices on a circle of the given radius, the first one pointing upwards.
+pub fn poly_regular(n: usize, radius: f32) -> Vec<Vector2> {
+    let (n, radius) = (n.max(3), radius.abs());
+
+    (0..n)
+        .map(|idx| polar(radius, FRAC_PI_2 - TAU * idx as f32 / n as f32))
+        .collect()
+}
+
+/// Rectangle whose corners are rounded by arcs of `segments` edges each.<br>
+/// The corner radius is clamped to half of the shorter side, a radius of 0 yields the plain rectangle.
+pub fn poly_rounded_rect(width: f32, height: f32, radius: f32, segments: usize) -> Vec<Vector2> {
+    let (hw, hh) = (0.5 * width.abs(), 0.5 * height.abs());
+    let r = radius.abs().min(hw.min(hh));
+    let segments = segments.max(1);
+
+    // corner centers clockwise from the top right, each with the angle its arc starts at
+    let corners = [
+        (Vector2::new(hw - r, hh - r), FRAC_PI_2),
+        (Vector2::new(hw - r, -hh + r), 0.),
+        (Vector2::new(-hw + r, -hh + r), -FRAC_PI_2),
+        (Vector2::new(-hw + r, hh - r), -PI),
+    ];
+
+    let vertices = corners
+        .iter()
+        .flat_map(|&(center, start)| {
+            (0..=segments)
+                .map(move |idx| center + polar(r, start - FRAC_PI_2 * idx as f32 / segments as f32))
+        })
+        .collect();
+
+    dedup_vertices(vertices)
+}
+
+/// Star with `points` (at least 2) tips, alternating between the outer and inner radius.<br>
+/// Stars are concave, so they are meant for rendering and spawn regions rather than bodies.
+pub fn poly_star(points: usize, outer_radius: f32, inner_radius: f32) -> Vec<Vector2> {
+    let n = 2 * points.max(2);
+    let (outer_radius, inner_radius) = (outer_radius.abs(), inner_radius.abs());
+
+    (0..n)
+        .map(|idx| {
+            let r = if idx % 2 == 0 {
+                outer_radius
+            } else {
+                inner_radius
+            };
+
+            polar(r, FRAC_PI_2 - TAU * idx as f32 / n as f32)
+        })
+        .collect()
+}
+
+/// Horizontal capsule, `length` being the distance between the centers of its two half circles,
+/// each approximated by `segments` edges.
+pub fn poly_capsule(length: f32, radius: f32, segments: usize) -> Vec<Vector2> {
+    let (hl, radius) = (0.5 * length.abs(), radius.abs());
+    let segments = segments.max(1);
+
+    let cap = |center: Vector2, start: f32| {
+        (0..=segments)
+            .map(move |idx| center + polar(radius, start - PI * idx as f32 / segments as f32))
+    };
+
+    let vertices = cap(Vector2::new(hl, 0.), FRAC_PI_2)
+        .chain(cap(Vector2::new(-hl, 0.), -FRAC_PI_2))
+        .collect();
+
+    dedup_vertices(vertices)
+}
+
+/// Convex hull of `n` (at least 3) random points within a circle of the given radius.<br>
+/// Falls back to a triangle, if the points keep being collinear.
+pub fn poly_random_convex(n: usize, radius: f32, rng: &mut impl Rng) -> Vec<Vector2> {
+    let (n, radius) = (n.max(3), radius.abs());
+
+    for _ in 0..RANDOM_HULL_ATTEMPTS {
+        let points: Vec<Vector2> = (0..n)
+            .map(|_| {
+                // square root, so the points are not clustered around the center
+                let r = radius * rng.gen::<f32>().sqrt();
+                polar(r, rng.gen_range(0.0..TAU))
+            })
+            .collect();
+
+        let hull = convex_hull(&points);
+
+        if hull.len() >= 3 {
+            let centroid = hull.iter().fold(Vector2::new(0., 0.), |sum, v| sum + *v)
+                * (1. / hull.len() as f32);
+
+            return hull
+                .into_iter()
+                .map(|v| Vector2::new(v.x - centroid.x, v.y - centroid.y))
+                .collect();
+        }
+    }
+
+    poly_regular(3, radius)
+}
+
+/// Convex hull in clockwise order via Andrew's monotone chain, collinear points are dropped.
+pub fn convex_hull(points: &[Vector2]) -> Vec<Vector2> {
+    let mut sorted = points.to_vec();
+    sorted.sort_by(|a, b| a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y)));
+
+    if sorted.len() < 3 {
+        return sorted;
+    }
+
+    // lower and upper chain in counter-clockwise order, each without its last point
+    let mut hull: Vec<Vector2> = Vec::with_capacity(2 * sorted.len());
+
+    for pass in [sorted.clone(), sorted.into_iter().rev().collect()] {
+        let start = hull.len();
+
+        for p in pass {
+            while hull.len() >= start + 2
+                && cross(hull[hull.len() - 2], hull[hull.len() - 1], p) <= 0.
+            {
+                hull.pop();
+            }
+
+            hull.push(p);
+        }
+
+        hull.pop();
+    }
+
+    hull.reverse();
+    hull
+}
+
+fn polar(r: f32, angle: f32) -> Vector2 {
+    Vector2::new(r * angle.cos(), r * angle.sin())
+}
+
+/// Removes consecutive (and wrapping) vertices that coincide, e.g. where arcs meet.
+fn dedup_vertices(mut vertices: Vec<Vector2>) -> Vec<Vector2> {
+    let close = |a: &Vector2, b: &Vector2| {
+        (a.x - b.x).abs() < MIN_VERTEX_DISTANCE && (a.y - b.y).abs() < MIN_VERTEX_DISTANCE
+    };
+
+    vertices.dedup_by(|b, a| close(a, b));
+
+    while vertices.len() > 1 && close(&vertices[0], &vertices[vertices.len() - 1]) {
+        vertices.pop();
+    }
+
+    vertices
+}
+
+// ------------------- Vector math -------------------
+pub fn dot(a: Vector2, b: Vector2) -> f32 {
+    a.x * b.x + a.y * b.y
+}
+
+pub fn sub(a: Vector2, b: Vector2) -> Vector2 {
+    Vector2::new(a.x - b.x, a.y - b.y)
+}
+
+pub fn scale(v: Vector2, s: f32) -> Vector2 {
+    Vector2::new(v.x * s, v.y * s)
+}
+
+pub fn length_sq(v: Vector2) -> f32 {
+    dot(v, v)
+}
+
+/// Unit vector in the direction of `v`, `None` for (almost) zero vectors.
+pub fn normalize(v: Vector2) -> Option<Vector2> {
+    let len = length_sq(v).sqrt();
+
+    if len <= f32::EPSILON {
+        None
+    } else {
+        Some(scale(v, 1. / len))
+    }
+}
+
+/// `v` rotated counter-clockwise by `angle` radians around the origin.
+pub fn rotate(v: Vector2, angle: f32) -> Vector2 {
+    let (sin, cos) = angle.sin_cos();
+    Vector2::new(v.x * cos - v.y * sin, v.x * sin + v.y * cos)
+}
+
+/// Cross product of `o -> a` and `o -> b`, positive iff `o`, `a`, `b` turn counter-clockwise.
+pub fn cross(o: Vector2, a: Vector2, b: Vector2) -> f32 {
+    (a.x - o.x) * (b.y - o.y) - (a.y - o.y) * (b.x - o.x)
+}
+
+// ------------------- Body rotation -------------------
+/// Dynamic body rotated by `angle` radians around its location, keeping its name and motion.<br>
+/// The engine derives vertices and hitbox when a body is built, so polygons are rebuilt from their rotated vertices
+/// and circles get their reference vertex rotated. AABBs cannot rotate and are returned unchanged.
+pub fn rotated_body(body: &Body, angle: f32) -> Result<Body, PolygonError> {
+    let mut rotated = match &body.shape {
+        Shape::AABB(_) => return Ok(body.clone()),
+        Shape::Circle(_) => {
+            let mut circle = body.clone();
+
+            for v in &mut circle.vertices {
+                *v = rotate(*v, angle);
+            }
+
+            return Ok(circle);
+        }
+        Shape::Polygon(_) => {
+            let loc = body.transform.location;
+            let vertices = body
+                .get_vertices_as_vec()
+                .into_iter()
+                .map(|v| rotate(v, angle))
+                .collect();
+
+            checked_polygon(loc.x, loc.y, vertices, body.material.clone())?
+        }
+    };
+
+    rotated.name = body.name;
+    rotated.transform.velocity = body.transform.velocity;
+    rotated.transform.angular_velocity = body.transform.angular_velocity;
+
+    Ok(rotated)
+}
+
+// ------------------- Polygon triangulation -------------------
+/// Signed area of a polygon, positive iff the vertices are in counter-clockwise order.
+pub fn signed_area(vertices: &[Vector2]) -> f32 {
+    let n = vertices.len();
+
+    0.5 * (0..n)
+        .map(|idx| {
+            let (a, b) = (vertices[idx], vertices[(idx + 1) % n]);
+            a.x * b.y - b.x * a.y
+        })
+        .sum::<f32>()
+}
+
+/// Triangulates a simple (convex or concave) polygon of either winding order via ear clipping.<br>
+/// Returns triangles as indices into `vertices`. Degenerate input that has no ear left is fanned.
+pub fn triangulate(vertices: &[Vector2]) -> Vec<[usize; 3]> {
+    let n = vertices.len();
+
+    if n < 3 {
+        return Vec::new();
+    }
+
+    let ccw = signed_area(vertices) > 0.;
+    let mut remaining: Vec<usize> = (0..n).collect();
+    let mut triangles: Vec<[usize; 3]> = Vec::with_capacity(n - 2);
+
+    while remaining.len() > 3 {
+        let m = remaining.len();
+
+        let ear = (0..m).find(|&idx| {
+            let (a, b, c) = (
+                remaining[(idx + m - 1) % m],
+                remaining[idx],
+                remaining[(idx + 1) % m],
+            );
+
+            is_convex_corner(vertices[a], vertices[b], vertices[c], ccw)
+                && !remaining.iter().any(|&p| {
+                    p != a
+                        && p != b
+                        && p != c
+                        && point_in_triangle(vertices[p], vertices[a], vertices[b], vertices[c])
+                })
+        });
+
+        match ear {
+            Some(idx) => {
+                triangles.push([
+                    remaining[(idx + m - 1) % m],
+                    remaining[idx],
+                    remaining[(idx + 1) % m],
+                ]);
+                remaining.remove(idx);
+            }
+            None => break,
+        }
+    }
+
+    for idx in 1..remaining.len() - 1 {
+        triangles.push([remaining[0], remaining[idx], remaining[idx + 1]]);
+    }
+
+    triangles
+}
+
+fn is_convex_corner(prev: Vector2, corner: Vector2, next: Vector2, ccw: bool) -> bool {
+    let turn = cross(prev, corner, next);
+
+    if ccw {
+        turn > 0.
+    } else {
+        turn < 0.
+    }
+}
+
+fn point_in_triangle(p: Vector2, a: Vector2, b: Vector2, c: Vector2) -> bool {
+    let (d1, d2, d3) = (cross(a, b, p), cross(b, c, p), cross(c, a, p));
+
+    let has_neg = d1 < 0. || d2 < 0. || d3 < 0.;
+    let has_pos = d1 > 0. || d2 > 0. || d3 > 0.;
+
+    !(has_neg && has_pos)
+}
+
 // concave testing
 pub fn poly_deep_concave(scale: f32) -> Vec<Vector2> {
     vec![
@@ -169,6 +530,113 @@ pub fn poly_shallow_concave(scale: f32) -> Vec<Vector2> {
     ]
 }
 
+// ------------------- Attractor field utility -------------------
+/// Combined pull of all attractors onto a body of unit mass at `point`.<br>
+/// Mirrors the engine: the distance gets clamped via `clamp_distance` and local attractors only pull within their radius.
+pub fn attractor_pull(attractors: &[Attractor], point: Vector2) -> Vector2 {
+    let mut pull = Vector2::new(0., 0.);
+
+    for attractor in attractors {
+        let dx = attractor.location.x - point.x;
+        let dy = attractor.location.y - point.y;
+        let distance = (dx * dx + dy * dy).sqrt();
+
+        if distance <= f32::EPSILON {
+            continue;
+        }
+
+        if attractor.a_type == Local && distance > attractor.r {
+            continue;
+        }
+
+        let clamped = distance.clamp(attractor.min_distance, attractor.max_distance);
+        let strength = attractor.mass / (clamped * clamped);
+
+        pull = pull + Vector2::new(dx / distance, dy / distance) * strength;
+    }
+
+    pull
+}
+
+// ------------------- Energy and momentum utility -------------------
+// static bodies do not move and are therefore excluded
+
+/// Total translational and rotational kinetic energy of all dynamic bodies in joules.
+pub fn kinetic_energy(bodies: &[Body]) -> f32 {
+    bodies
+        .iter()
+        .filter(|b| b.body_type == Dynamic)
+        .map(|b| {
+            let v = b.transform.velocity;
+            let w = b.transform.angular_velocity;
+            0.5 * b.mass * (v.x * v.x + v.y * v.y) + 0.5 * b.inertia * w * w
+        })
+        .sum()
+}
+
+/// Total linear momentum of all dynamic bodies in Ns.
+pub fn linear_momentum(bodies: &[Body]) -> Vector2 {
+    bodies
+        .iter()
+        .filter(|b| b.body_type == Dynamic)
+        .fold(Vector2::new(0., 0.), |p, b| {
+            p + b.transform.velocity * b.mass
+        })
+}
+
+/// Total angular momentum of all dynamic bodies around the world origin in Nms,
+/// consisting of the orbital part `r x mv` and the spin `I * w`.
+pub fn angular_momentum(bodies: &[Body]) -> f32 {
+    bodies
+        .iter()
+        .filter(|b| b.body_type == Dynamic)
+        .map(|b| {
+            let (r, v) = (b.transform.location, b.transform.velocity);
+            b.mass * (r.x * v.y - r.y * v.x) + b.inertia * b.transform.angular_velocity
+        })
+        .sum()
+}
+
+/// Acceleration of `force::GRAVITY_EARTH`, the engine force itself only exposes its effect on bodies.
+pub const GRAVITY_EARTH_ACCELERATION: Vector2 = Vector2 { x: 0., y: -9.81 };
+
+/// Total potential energy of all dynamic bodies in joules,
+/// within the uniform acceleration `gravity` and the fields of all attractors.<br>
+/// Attractor potentials use the same distance clamping as `attractor_pull` and, like it, skip bodies at the center of an attractor.
+/// Local attractors only count within their radius.
+pub fn potential_energy(bodies: &[Body], attractors: &[Attractor], gravity: Vector2) -> f32 {
+    bodies
+        .iter()
+        .filter(|b| b.body_type == Dynamic)
+        .map(|b| {
+            let loc = b.transform.location;
+            let uniform = -b.mass * (gravity.x * loc.x + gravity.y * loc.y);
+
+            let attracted: f32 = attractors
+                .iter()
+                .map(|attractor| {
+                    let dx = attractor.location.x - loc.x;
+                    let dy = attractor.location.y - loc.y;
+                    let distance = (dx * dx + dy * dy).sqrt();
+
+                    if distance <= f32::EPSILON {
+                        return 0.;
+                    }
+
+                    if attractor.a_type == Local && distance > attractor.r {
+                        return 0.;
+                    }
+
+                    let clamped = distance.clamp(attractor.min_distance, attractor.max_distance);
+                    -attractor.mass * b.mass / clamped
+                })
+                .sum();
+
+            uniform + attracted
+        })
+        .sum()
+}
+
 // ------------------- Solar scene utility -------------------
 pub fn solar_bodies() -> (Attractor, Vec<Body>) {
     // (name, distance to sun, mass, orbital velocity, radius)
@@ -238,32 +706,85 @@ pub fn planet(
 pub enum SpawnerType {
     Single,
     Pipeline,
+    /// draws from `templates` according to their weights
+    Weighted,
+}
+
+/// Initial velocity of spawned bodies, emitted in a cone around `direction`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EmissionCone {
+    /// angle in radians, 0 pointing to the right and PI/2 upwards
+    pub direction: f32,
+    /// maximal deviation from `direction` to either side in radians
+    pub spread: f32,
+    pub min_speed: f32,
+    pub max_speed: f32,
+}
+
+impl EmissionCone {
+    pub fn sample(&self, rng: &mut impl Rng) -> Vector2 {
+        let angle = self.direction + random_in(rng, -self.spread, self.spread);
+        let speed = random_in(rng, self.min_speed, self.max_speed);
+
+        Vector2::new(angle.cos(), angle.sin()) * speed
+    }
 }
 
+/// Uniform sample within `[min, max]`, that also accepts empty ranges.
+fn random_in(rng: &mut impl Rng, min: f32, max: f32) -> f32 {
+    if max > min {
+        rng.gen_range(min..=max)
+    } else {
+        min
+    }
+}
+
+/// Seed of spawners, that are not given one via `with_seed`.
+pub const DEFAULT_SPAWNER_SEED: u64 = 0;
+
 #[derive(Debug)]
 pub struct BodySpawner {
     pub body: Body,
     pub body_pipeline: Vec<Body>,
-    pub amount: u8,
-    pub count: u8,
-    pub timer: Instant,
+    pub templates: Vec<BodyTemplate>,
+    /// `None` spawns indefinitely (single and weighted spawners)
+    pub amount: Option<u32>,
+    pub count: u32,
+    /// bodies per trigger
+    pub burst: u32,
+    pub paused: bool,
+    /// simulation time of the last spawn in seconds
+    pub last_spawn: f32,
     pub period: f32,
     pub t: SpawnerType,
-    pub offset: f32,
+    pub region: SpawnRegion,
+    pub sampler: RegionSampler,
+    pub emission: Option<EmissionCone>,
+    /// range of the initial angular velocity in rad/s
+    pub spin: (f32, f32),
+    pub random_rotation: bool,
+    /// seconds of simulation time until spawned bodies get removed, see `Despawner`
+    pub lifetime: Option<f32>,
+    /// source of all random spawn properties, seeded so every run spawns the same sequence
+    pub rng: StdRng,
 }
 
 impl BodySpawner {
     pub fn new_single_type(
         body: Body,
-        amount: u8,
+        amount: u32,
         frequency_in_hz: f32,
         offset: f32,
     ) -> BodySpawner {
         BodySpawner {
-            body,
-            amount,
+            amount: Some(amount),
             period: 1. / frequency_in_hz,
-            offset,
+            region: SpawnRegion::Rectangle {
+                center: body.transform.location,
+                half_width: offset,
+                half_height: offset,
+            },
+            body,
             ..Default::default()
         }
     }
@@ -276,44 +797,265 @@ impl BodySpawner {
             ..Default::default()
         }
     }
+
+    /// Draws bodies from weighted templates at `location`, up to `amount` or indefinitely if `None`.<br>
+    /// Without any template of positive weight, the spawner spawns nothing.
+    pub fn new_weighted(
+        templates: Vec<BodyTemplate>,
+        location: Vector2,
+        amount: Option<u32>,
+        frequency_in_hz: f32,
+    ) -> BodySpawner {
+        let mut body = Body::default();
+        body.transform.location = location;
+
+        BodySpawner {
+            body,
+            templates,
+            amount,
+            period: 1. / frequency_in_hz,
+            t: SpawnerType::Weighted,
+            ..Default::default()
+        }
+    }
+
+    /// Spawns indefinitely, pipelines still end with their last body.
+    pub fn unlimited(mut self) -> BodySpawner {
+        self.amount = None;
+        self
+    }
+
+    /// Spawns `bodies_per_trigger` bodies at once, each spawn period.
+    pub fn with_burst(mut self, bodies_per_trigger: u32) -> BodySpawner {
+        self.burst = bodies_per_trigger.max(1);
+        self
+    }
+
+    /// Spawns bodies within the given region instead of at the location of the spawned body.
+    pub fn with_region(mut self, region: SpawnRegion) -> BodySpawner {
+        self.region = region;
+        self
+    }
+
+    /// Keeps new bodies at least `min_distance` apart from the previously spawned ones where possible.
+    pub fn with_poisson_disk(mut self, min_distance: f32) -> BodySpawner {
+        self.sampler = RegionSampler::new(SpawnSampling::PoissonDisk { min_distance });
+        self
+    }
+
+    /// Emits bodies in a cone around `direction` (radians) with up to `spread` deviation to either side.
+    pub fn with_emission(
+        mut self,
+        direction: f32,
+        spread: f32,
+        min_speed: f32,
+        max_speed: f32,
+    ) -> BodySpawner {
+        self.emission = Some(EmissionCone {
+            direction,
+            spread,
+            min_speed,
+            max_speed,
+        });
+        self
+    }
+
+    pub fn with_spin(
+        mut self,
+        min_angular_velocity: f32,
+        max_angular_velocity: f32,
+    ) -> BodySpawner {
+        self.spin = (min_angular_velocity, max_angular_velocity);
+        self
+    }
+
+    /// Rotates spawned bodies by a random angle, see `rotated_body`.
+    pub fn with_random_rotation(mut self) -> BodySpawner {
+        self.random_rotation = true;
+        self
+    }
+
+    pub fn with_lifetime(mut self, lifetime_in_secs: f32) -> BodySpawner {
+        self.lifetime = Some(lifetime_in_secs);
+        self
+    }
+
+    /// Seeds locations, emission, spin, rotation and template picks, spawners default to `DEFAULT_SPAWNER_SEED`.
+    pub fn with_seed(mut self, seed: u64) -> BodySpawner {
+        self.rng = StdRng::seed_from_u64(seed);
+        self
+    }
 }
 
 impl BodySpawner {
-    pub fn spawn(&mut self) -> Body {
-        match self.t {
-            SpawnerType::Single => {
-                self.count += 1;
-                let mut rng = rand::thread_rng();
+    /// `None` if nothing could be spawned, e.g. because the picked template is invalid.
+    pub fn spawn(&mut self) -> Option<Body> {
+        let mut b = self.next_body()?;
 
-                let mut offset_x: f32 = 0.0;
-                let mut offset_y: f32 = 0.0;
+        b.transform.location =
+            self.sampler
+                .sample(&self.region, b.transform.location, &mut self.rng);
 
-                if self.offset != 0. {
-                    offset_x = rng.gen_range(-self.offset..self.offset);
-                    offset_y = rng.gen_range(-self.offset..self.offset);
-                }
+        if b.body_type != Dynamic {
+            return Some(b);
+        }
+
+        if let Some(emission) = self.emission {
+            b.transform.velocity = emission.sample(&mut self.rng);
+        }
+
+        if self.spin != (0., 0.) {
+            b.transform.angular_velocity = random_in(&mut self.rng, self.spin.0, self.spin.1);
+        }
+
+        if self.random_rotation {
+            let angle = self.rng.gen_range(0.0..TAU);
 
-                let mut b = self.body.clone();
-                b.transform.location.x += offset_x;
-                b.transform.location.y += offset_y;
-                b
+            match rotated_body(&b, angle) {
+                Ok(rotated) => b = rotated,
+                Err(err) => eprintln!("could not rotate spawned body: {err}"),
             }
+        }
+
+        Some(b)
+    }
+
+    /// Location of the spawned body, pipelines use the location of their next body.
+    pub fn location(&self) -> Vector2 {
+        match self.t {
             SpawnerType::Pipeline => self
                 .body_pipeline
-                .pop()
-                .expect("should be checked in is_spawnable()"),
+                .last()
+                .map_or(self.body.transform.location, |b| b.transform.location),
+            _ => self.body.transform.location,
         }
     }
 
-    pub fn is_spawnable(&mut self) -> bool {
+    /// Moves the spawner including its region, so that `location()` ends up at `location`.
+    pub fn move_to(&mut self, location: Vector2) {
+        let current = self.location();
+        let delta = Vector2::new(location.x - current.x, location.y - current.y);
+
+        self.region.translate(delta);
+        self.body.transform.location = self.body.transform.location + delta;
+
+        for b in &mut self.body_pipeline {
+            b.transform.location = b.transform.location + delta;
+        }
+    }
+
+    /// Spawns up to `burst` bodies, as many as are left.
+    pub fn spawn_burst(&mut self) -> Vec<Body> {
+        let burst = match self.remaining() {
+            Some(remaining) => remaining.min(self.burst as usize),
+            None => self.burst as usize,
+        };
+
+        (0..burst).filter_map(|_| self.spawn()).collect()
+    }
+
+    /// Number of bodies left to spawn, `None` if unlimited.
+    pub fn remaining(&self) -> Option<usize> {
+        match self.t {
+            SpawnerType::Pipeline => Some(self.body_pipeline.len()),
+            _ => self
+                .amount
+                .map(|amount| amount.saturating_sub(self.count) as usize),
+        }
+    }
+
+    fn next_body(&mut self) -> Option<Body> {
         match self.t {
             SpawnerType::Single => {
-                self.count < self.amount && self.timer.elapsed().as_secs_f32() >= self.period
+                self.count = self.count.saturating_add(1);
+                Some(self.body.clone())
+            }
+            SpawnerType::Weighted => {
+                let location = self.body.transform.location;
+
+                let idx = pick_weighted(&self.templates, &mut self.rng)?;
+
+                match self.templates[idx].instantiate(location, &mut self.rng) {
+                    Ok(body) => {
+                        self.count = self.count.saturating_add(1);
+                        Some(body)
+                    }
+                    Err(err) => {
+                        // reported once, the template is not picked again
+                        eprintln!("disabled invalid spawner template {idx}: {err}");
+                        self.templates[idx].weight = 0.;
+                        None
+                    }
+                }
             }
-            SpawnerType::Pipeline => {
-                !self.body_pipeline.is_empty() && self.timer.elapsed().as_secs_f32() >= self.period
+            SpawnerType::Pipeline => self.body_pipeline.pop(),
+        }
+    }
+
+    /// Spawns all remaining bodies at once, ignoring the spawn period.<br>
+    /// Unlimited spawners have no remaining bodies and spawn nothing.
+    pub fn drain(&mut self) -> Vec<Body> {
+        if !self.has_templates() {
+            return Vec::new();
+        }
+
+        let remaining = self.remaining().unwrap_or(0);
+        (0..remaining).filter_map(|_| self.spawn()).collect()
+    }
+
+    /// Whether a spawn is due at the simulation time `now` in seconds.
+    pub fn is_spawnable(&self, now: f32) -> bool {
+        !self.paused
+            && self.has_templates()
+            && self.remaining() != Some(0)
+            && now - self.last_spawn >= self.period
+    }
+
+    /// Weighted spawners need at least one template with a positive weight, all others always have a body.
+    pub fn has_templates(&self) -> bool {
+        !matches!(self.t, SpawnerType::Weighted) || self.templates.iter().any(|t| t.weight > 0.)
+    }
+
+    /// Spawns all bursts that are due at the simulation time `now` in seconds.<br>
+    /// Missed periods are caught up, so the spawn sequence only depends on the simulation time
+    /// and not on how often this gets called.
+    pub fn spawn_due(&mut self, now: f32) -> Vec<Body> {
+        let mut bodies = Vec::new();
+
+        if self.paused {
+            // paused time does not count towards the schedule
+            self.last_spawn = self.last_spawn.max(now - self.period);
+            return bodies;
+        }
+
+        while self.is_spawnable(now) {
+            bodies.extend(self.spawn_burst());
+
+            if self.period > 0. {
+                self.last_spawn += self.period;
+            } else {
+                self.last_spawn = now;
+                break;
             }
         }
+
+        bodies
+    }
+
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    pub fn toggle(&mut self) {
+        if self.paused {
+            self.resume();
+        } else {
+            self.pause();
+        }
     }
 }
 
@@ -322,12 +1064,185 @@ impl Default for BodySpawner {
         Self {
             body: Default::default(),
             body_pipeline: Default::default(),
-            amount: 1,
+            templates: Default::default(),
+            amount: Some(1),
             count: 0,
-            timer: Instant::now(),
+            burst: 1,
+            paused: false,
+            last_spawn: 0.,
             period: 1.,
             t: SpawnerType::Single,
-            offset: 0.0,
+            region: SpawnRegion::Point,
+            sampler: RegionSampler::default(),
+            emission: None,
+            spin: (0., 0.),
+            random_rotation: false,
+            lifetime: None,
+            rng: StdRng::seed_from_u64(DEFAULT_SPAWNER_SEED),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::polygon_validation::{validate_polygon, PolygonError};
+
+    fn triangle_area(vertices: &[Vector2], [a, b, c]: [usize; 3]) -> f32 {
+        0.5 * cross(vertices[a], vertices[b], vertices[c]).abs()
+    }
+
+    /// Clockwise arrow pointing upwards, with reflex corners at both sides of the shaft.
+    fn arrow() -> Vec<Vector2> {
+        vec![
+            Vector2::new(0., 2.),
+            Vector2::new(1., 1.),
+            Vector2::new(0.3, 1.),
+            Vector2::new(0.3, -1.),
+            Vector2::new(-0.3, -1.),
+            Vector2::new(-0.3, 1.),
+            Vector2::new(-1., 1.),
+        ]
+    }
+
+    #[test]
+    fn triangulation_of_concave_polygon_covers_its_area() {
+        for vertices in [arrow(), arrow().into_iter().rev().collect()] {
+            let triangles = triangulate(&vertices);
+            let area: f32 = triangles.iter().map(|t| triangle_area(&vertices, *t)).sum();
+
+            assert_eq!(triangles.len(), vertices.len() - 2);
+            assert!((area - signed_area(&vertices).abs()).abs() < 1e-5);
         }
     }
+
+    #[test]
+    fn triangulation_of_concave_polygon_has_no_degenerate_triangles() {
+        let vertices = arrow();
+
+        for triangle in triangulate(&vertices) {
+            assert!(triangle_area(&vertices, triangle) > 1e-5);
+        }
+    }
+
+    fn assert_clockwise_and_valid(vertices: &[Vector2]) {
+        assert!(signed_area(vertices) < 0.);
+        assert_eq!(validate_polygon(vertices), Ok(()));
+    }
+
+    #[test]
+    fn convex_generators_are_clockwise_and_valid() {
+        let mut rng = StdRng::seed_from_u64(0);
+
+        for n in [3, 4, 7, 32] {
+            assert_clockwise_and_valid(&poly_regular(n, 1.));
+        }
+
+        assert_clockwise_and_valid(&poly_rounded_rect(2., 1., 0.25, 4));
+        assert_clockwise_and_valid(&poly_rounded_rect(2., 1., 0., 4));
+        assert_clockwise_and_valid(&poly_rounded_rect(1., 1., 0.5, 8));
+        assert_clockwise_and_valid(&poly_capsule(2., 0.5, 6));
+        assert_clockwise_and_valid(&poly_capsule(0., 0.5, 6));
+
+        for _ in 0..50 {
+            assert_clockwise_and_valid(&poly_random_convex(8, 1., &mut rng));
+        }
+    }
+
+    #[test]
+    fn star_is_clockwise_and_concave() {
+        let star = poly_star(5, 1., 0.4);
+
+        assert_eq!(star.len(), 10);
+        assert!(signed_area(&star) < 0.);
+        assert!(matches!(
+            validate_polygon(&star),
+            Err(PolygonError::Concave(_))
+        ));
+    }
+
+    #[test]
+    fn rounded_rect_without_radius_is_the_plain_rectangle() {
+        assert_eq!(poly_rounded_rect(2., 1., 0., 4).len(), 4);
+    }
+
+    #[test]
+    fn generators_guard_their_inputs() {
+        assert_eq!(poly_regular(1, 1.).len(), 3);
+        assert_clockwise_and_valid(&poly_regular(5, -1.));
+        assert_clockwise_and_valid(&poly_rounded_rect(-2., -1., 5., 0));
+        assert_clockwise_and_valid(&poly_capsule(-1., -0.5, 0));
+        assert_eq!(poly_star(0, 1., 0.5).len(), 4);
+    }
+
+    #[test]
+    fn convex_hull_drops_inner_and_collinear_points() {
+        let points: Vec<Vector2> = [
+            (0., 0.),
+            (1., 0.),
+            (2., 0.),
+            (2., 2.),
+            (1., 1.),
+            (0.5, 1.5),
+            (0., 2.),
+            (2., 2.),
+        ]
+        .iter()
+        .map(|&(x, y)| Vector2::new(x, y))
+        .collect();
+
+        let hull = convex_hull(&points);
+        let corners: Vec<(f32, f32)> = hull.iter().map(|v| (v.x, v.y)).collect();
+
+        assert_eq!(corners.len(), 4);
+        assert!(signed_area(&hull) < 0.);
+
+        for corner in [(0., 0.), (2., 0.), (2., 2.), (0., 2.)] {
+            assert!(corners.contains(&corner));
+        }
+    }
+
+    #[test]
+    fn convex_hull_of_collinear_points_is_degenerate() {
+        let points: Vec<Vector2> = (0..5).map(|idx| Vector2::new(idx as f32, 0.)).collect();
+        assert!(convex_hull(&points).len() < 3);
+    }
+
+    #[test]
+    fn potential_energy_is_finite_at_the_center_of_an_attractor() {
+        let attractor = Attractor::new(0., 0., 1., Global, None).clamp_distance(0., 10.);
+        let body = Body::circle(0., 0., 0.1, material::DEFAULT);
+        let zero = Vector2::new(0., 0.);
+
+        assert!(potential_energy(&[body], &[attractor], zero).is_finite());
+    }
+
+    #[test]
+    fn rotation_is_counter_clockwise() {
+        let v = rotate(Vector2::new(1., 0.), FRAC_PI_2);
+        assert!(v.x.abs() < 1e-6 && (v.y - 1.).abs() < 1e-6);
+
+        let v = rotate(Vector2::new(1., 2.), TAU);
+        assert!((v.x - 1.).abs() < 1e-5 && (v.y - 2.).abs() < 1e-5);
+    }
+
+    #[test]
+    fn weighted_spawner_without_templates_spawns_nothing() {
+        let location = Vector2::new(0., 0.);
+        let zero_weight = BodyTemplate::new(TemplateShape::Circle, 0.1, 0.2, material::DEFAULT, 0.);
+
+        for templates in [Vec::new(), vec![zero_weight]] {
+            let mut spawner = BodySpawner::new_weighted(templates, location, Some(5), 10.);
+
+            assert!(!spawner.has_templates());
+            assert!(!spawner.is_spawnable(1.));
+            assert!(spawner.spawn_due(1.).is_empty());
+            assert!(spawner.drain().is_empty());
+        }
+    }
+
+    #[test]
+    fn triangulation_needs_at_least_three_vertices() {
+        assert!(triangulate(&[Vector2::new(0., 0.), Vector2::new(1., 0.)]).is_empty());
+    }
 }
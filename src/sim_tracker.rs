@@ -3,6 +3,10 @@
 
 use std::time::Instant;
 
+use rustycs::math::Vector2;
+
+use crate::{stats::TimingStats, svg::SvgSpace};
+
 #[derive(PartialEq)]
 pub enum ShowDebug {
     Visible,
@@ -26,6 +30,60 @@ impl ShowDebug {
     }
 }
 
+/// Visualization of the attractor field, cycles through all variants when toggled.
+#[derive(PartialEq)]
+pub enum FieldOverlay {
+    Hidden,
+    Arrows,
+    Heatmap,
+}
+
+impl Default for FieldOverlay {
+    fn default() -> Self {
+        Self::Hidden
+    }
+}
+
+impl FieldOverlay {
+    pub fn toggle(&mut self) {
+        use FieldOverlay::*;
+
+        match self {
+            Hidden => *self = Arrows,
+            Arrows => *self = Heatmap,
+            Heatmap => *self = Hidden,
+        }
+    }
+}
+
+/// Mouse interaction mode of the controller, cycles through all variants when toggled.
+#[derive(PartialEq)]
+pub enum EditMode {
+    Inactive,
+    /// place, select, move and delete spawners
+    Spawners,
+    /// place local attractors, their radius is set by dragging
+    Attractors,
+}
+
+impl Default for EditMode {
+    fn default() -> Self {
+        Self::Inactive
+    }
+}
+
+impl EditMode {
+    pub fn toggle(&mut self) {
+        use EditMode::*;
+
+        match self {
+            Inactive => *self = Spawners,
+            Spawners => *self = Attractors,
+            Attractors => *self = Inactive,
+        }
+    }
+}
+
 #[derive(PartialEq)]
 pub enum SimulationMode {
     Running,
@@ -52,9 +110,13 @@ impl SimulationMode {
 pub struct SimulationState {
     pub debug_information: ShowDebug,
     pub collision_points: ShowDebug,
+    pub contact_details: ShowDebug,
     pub hitboxes: ShowDebug,
+    pub sprites: ShowDebug,
     pub debug_grid: ShowDebug,
-    pub grid_ratio: f32,
+    pub graphs: ShowDebug,
+    pub conservation: ShowDebug,
+    pub attractor_field: FieldOverlay,
     pub simulation: SimulationMode,
     pub debug_instant: Instant,
     pub debug_timeout: f32,
@@ -67,7 +129,13 @@ pub struct SimulationState {
     pub update_instant: Instant,
     pub update_timeout: f32,
     pub nr_of_updates: u32,
-    pub max_update_duration: f32,
+    pub nr_of_removed: u32,
+    pub timings: TimingStats,
+    pub svg_export: Option<SvgSpace>,
+    pub edit_mode: EditMode,
+    pub selected_spawner: Option<usize>,
+    /// world position where the current mouse drag started
+    pub drag_start: Option<Vector2>,
 }
 
 impl SimulationState {
@@ -99,6 +167,11 @@ impl SimulationState {
     pub fn atomic_update_allowed(&self) -> bool {
         self.update_instant.elapsed().as_secs_f32() >= self.update_timeout
     }
+
+    /// Elapsed simulation time in seconds, one tick being `tick_timeout` long.
+    pub fn simulation_time(&self) -> f32 {
+        self.nr_of_updates as f32 * self.tick_timeout
+    }
 }
 
 impl Default for SimulationState {
@@ -109,11 +182,15 @@ impl Default for SimulationState {
         Self {
             debug_information: Visible,
             collision_points: Hidden,
+            contact_details: Hidden,
             hitboxes: Hidden,
+            sprites: Visible,
             debug_grid: Hidden,
+            graphs: Hidden,
+            conservation: Hidden,
+            attractor_field: FieldOverlay::Hidden,
             debug_instant: Instant::now(),
             debug_timeout: 0.25,
-            grid_ratio: 10.,
             simulation: Running,
             spawn_instant: Instant::now(),
             spawn_timeout: 0.15,
@@ -124,7 +201,12 @@ impl Default for SimulationState {
             update_instant: Instant::now(),
             update_timeout: 0.25,
             nr_of_updates: 0,
-            max_update_duration: 0.,
+            nr_of_removed: 0,
+            timings: TimingStats::default(),
+            svg_export: None,
+            edit_mode: EditMode::Inactive,
+            selected_spawner: None,
+            drag_start: None,
         }
     }
 }
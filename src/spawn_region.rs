@@ -0,0 +1,372 @@
+//! Regions that spawners sample the locations of new bodies from.<br>
+//! All regions are in world coordinates, except `Point`, which keeps the location of the spawned body.<br>
+//! Poisson-disk sampling keeps new locations apart from the previous ones, so consecutive spawns do not overlap.
+
+use std::collections::VecDeque;
+
+use rand::Rng;
+use rustycs::{entities::body::Body, math::Vector2, shapes::Shape};
+
+use crate::rustycs_utility as util;
+
+/// Candidates that are tried per Poisson-disk sample, before the best candidate is taken.
+const POISSON_DISK_ATTEMPTS: usize = 30;
+
+/// Number of previous locations, that Poisson-disk samples keep their distance to.
+const POISSON_DISK_MEMORY: usize = 64;
+
+#[derive(Clone, Debug)]
+pub enum SpawnRegion {
+    /// location of the spawned body itself
+    Point,
+    Rectangle {
+        center: Vector2,
+        half_width: f32,
+        half_height: f32,
+    },
+    Circle {
+        center: Vector2,
+        radius: f32,
+    },
+    Segment {
+        from: Vector2,
+        to: Vector2,
+    },
+    /// simple polygon of either winding order
+    Polygon(Vec<Vector2>),
+}
+
+impl SpawnRegion {
+    /// Segment along the top surface of a platform, lifted by `clearance` along the surface normal.<br>
+    /// The top surface is the edge whose outward normal points upwards the most, circles only offer their top point.
+    pub fn surface_of(platform: &Body, clearance: f32) -> SpawnRegion {
+        let loc = platform.transform.location;
+
+        if let Shape::Circle(c) = &platform.shape {
+            let top = Vector2::new(loc.x, loc.y + c.r + clearance);
+            return SpawnRegion::Segment { from: top, to: top };
+        }
+
+        let vertices: Vec<Vector2> = platform
+            .get_vertices_as_vec()
+            .into_iter()
+            .map(|v| v + loc)
+            .collect();
+
+        let n = vertices.len();
+        let ccw = util::signed_area(&vertices) > 0.;
+
+        let outward_normal = |idx: usize| {
+            let (a, b) = (vertices[idx], vertices[(idx + 1) % n]);
+            let (dx, dy) = (b.x - a.x, b.y - a.y);
+            let len = (dx * dx + dy * dy).sqrt().max(f32::EPSILON);
+
+            if ccw {
+                Vector2::new(dy / len, -dx / len)
+            } else {
+                Vector2::new(-dy / len, dx / len)
+            }
+        };
+
+        let top = (0..n)
+            .max_by(|a, b| outward_normal(*a).y.total_cmp(&outward_normal(*b).y))
+            .expect("platforms have at least 3 vertices");
+
+        let normal = outward_normal(top) * clearance;
+
+        SpawnRegion::Segment {
+            from: vertices[top] + normal,
+            to: vertices[(top + 1) % n] + normal,
+        }
+    }
+
+    pub fn translate(&mut self, delta: Vector2) {
+        match self {
+            SpawnRegion::Point => {}
+            SpawnRegion::Rectangle { center, .. } | SpawnRegion::Circle { center, .. } => {
+                *center = *center + delta;
+            }
+            SpawnRegion::Segment { from, to } => {
+                *from = *from + delta;
+                *to = *to + delta;
+            }
+            SpawnRegion::Polygon(vertices) => {
+                for v in vertices {
+                    *v = *v + delta;
+                }
+            }
+        }
+    }
+
+    /// Uniformly distributed location within the region, `origin` being the location of the spawned body.
+    pub fn sample(&self, origin: Vector2, rng: &mut impl Rng) -> Vector2 {
+        match self {
+            SpawnRegion::Point => origin,
+            SpawnRegion::Rectangle {
+                center,
+                half_width,
+                half_height,
+            } => Vector2::new(
+                center.x + symmetric(rng, *half_width),
+                center.y + symmetric(rng, *half_height),
+            ),
+            SpawnRegion::Circle { center, radius } => {
+                // square root, so the samples are not clustered around the center
+                let r = radius * rng.gen::<f32>().sqrt();
+                let angle = rng.gen_range(0.0..std::f32::consts::TAU);
+
+                Vector2::new(center.x + r * angle.cos(), center.y + r * angle.sin())
+            }
+            SpawnRegion::Segment { from, to } => {
+                let t: f32 = rng.gen();
+                Vector2::new(from.x + (to.x - from.x) * t, from.y + (to.y - from.y) * t)
+            }
+            SpawnRegion::Polygon(vertices) => sample_polygon(vertices, rng).unwrap_or(origin),
+        }
+    }
+}
+
+/// How locations are drawn from a `SpawnRegion`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SpawnSampling {
+    Uniform,
+    /// keeps at least `min_distance` to the previous locations where possible
+    PoissonDisk {
+        min_distance: f32,
+    },
+}
+
+impl Default for SpawnSampling {
+    fn default() -> Self {
+        Self::Uniform
+    }
+}
+
+/// Samples a region according to the sampling strategy and remembers previous locations.
+#[derive(Clone, Debug, Default)]
+pub struct RegionSampler {
+    pub sampling: SpawnSampling,
+    previous: VecDeque<Vector2>,
+}
+
+impl RegionSampler {
+    pub fn new(sampling: SpawnSampling) -> RegionSampler {
+        RegionSampler {
+            sampling,
+            previous: VecDeque::new(),
+        }
+    }
+
+    pub fn sample(&mut self, region: &SpawnRegion, origin: Vector2, rng: &mut impl Rng) -> Vector2 {
+        let location = match self.sampling {
+            SpawnSampling::Uniform => region.sample(origin, rng),
+            SpawnSampling::PoissonDisk { min_distance } => {
+                let mut best = region.sample(origin, rng);
+                let mut best_distance = self.nearest_distance(best);
+
+                // dart throwing, falling back to the candidate farthest away from all others
+                for _ in 1..POISSON_DISK_ATTEMPTS {
+                    if best_distance >= min_distance {
+                        break;
+                    }
+
+                    let candidate = region.sample(origin, rng);
+                    let distance = self.nearest_distance(candidate);
+
+                    if distance > best_distance {
+                        best = candidate;
+                        best_distance = distance;
+                    }
+                }
+
+                best
+            }
+        };
+
+        if self.previous.len() == POISSON_DISK_MEMORY {
+            self.previous.pop_front();
+        }
+
+        self.previous.push_back(location);
+        location
+    }
+
+    fn nearest_distance(&self, point: Vector2) -> f32 {
+        self.previous
+            .iter()
+            .map(|p| ((p.x - point.x).powi(2) + (p.y - point.y).powi(2)).sqrt())
+            .fold(f32::MAX, f32::min)
+    }
+}
+
+fn symmetric(rng: &mut impl Rng, half: f32) -> f32 {
+    if half > 0. {
+        rng.gen_range(-half..half)
+    } else {
+        0.
+    }
+}
+
+/// Uniform sample within a polygon, by choosing a triangle weighted by its area.
+fn sample_polygon(vertices: &[Vector2], rng: &mut impl Rng) -> Option<Vector2> {
+    let triangles = util::triangulate(vertices);
+
+    let area = |[a, b, c]: &[usize; 3]| {
+        let (a, b, c) = (vertices[*a], vertices[*b], vertices[*c]);
+        0.5 * ((b.x - a.x) * (c.y - a.y) - (b.y - a.y) * (c.x - a.x)).abs()
+    };
+
+    let total: f32 = triangles.iter().map(area).sum();
+
+    if total <= f32::EPSILON {
+        return None;
+    }
+
+    let mut pick = rng.gen_range(0.0..total);
+    let [a, b, c] = *triangles
+        .iter()
+        .find(|t| {
+            pick -= area(t);
+            pick <= 0.
+        })
+        .or(triangles.last())?;
+
+    let (a, b, c) = (vertices[a], vertices[b], vertices[c]);
+    let (mut u, mut v): (f32, f32) = (rng.gen(), rng.gen());
+
+    // reflect samples of the parallelogram back into the triangle
+    if u + v > 1. {
+        u = 1. - u;
+        v = 1. - v;
+    }
+
+    Some(Vector2::new(
+        a.x + (b.x - a.x) * u + (c.x - a.x) * v,
+        a.y + (b.y - a.y) * u + (c.y - a.y) * v,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+
+    const SAMPLES: usize = 500;
+
+    fn distance(a: Vector2, b: Vector2) -> f32 {
+        ((a.x - b.x).powi(2) + (a.y - b.y).powi(2)).sqrt()
+    }
+
+    /// Even-odd rule, points on the boundary count as either.
+    fn contains(vertices: &[Vector2], p: Vector2) -> bool {
+        let n = vertices.len();
+        let mut inside = false;
+
+        for idx in 0..n {
+            let (a, b) = (vertices[idx], vertices[(idx + 1) % n]);
+
+            if (a.y > p.y) != (b.y > p.y) && p.x < a.x + (p.y - a.y) / (b.y - a.y) * (b.x - a.x) {
+                inside = !inside;
+            }
+        }
+
+        inside
+    }
+
+    #[test]
+    fn poisson_disk_samples_keep_their_distance() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let region = SpawnRegion::Rectangle {
+            center: Vector2::new(0., 0.),
+            half_width: 10.,
+            half_height: 10.,
+        };
+
+        // few enough samples for the region, so every sample finds a free spot
+        let mut sampler = RegionSampler::new(SpawnSampling::PoissonDisk { min_distance: 1. });
+        let samples: Vec<Vector2> = (0..20)
+            .map(|_| sampler.sample(&region, Vector2::new(0., 0.), &mut rng))
+            .collect();
+
+        for (idx, a) in samples.iter().enumerate() {
+            for b in &samples[idx + 1..] {
+                assert!(distance(*a, *b) >= 1.);
+            }
+        }
+    }
+
+    #[test]
+    fn poisson_disk_samples_stay_within_the_region() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let center = Vector2::new(3., -2.);
+        let region = SpawnRegion::Circle { center, radius: 2. };
+
+        // more samples than fit, so the fallback to the farthest candidate is used as well
+        let mut sampler = RegionSampler::new(SpawnSampling::PoissonDisk { min_distance: 1. });
+
+        for _ in 0..SAMPLES {
+            let p = sampler.sample(&region, Vector2::new(0., 0.), &mut rng);
+            assert!(distance(p, center) <= 2. + 1e-5);
+        }
+    }
+
+    #[test]
+    fn polygon_samples_are_contained_in_concave_polygons() {
+        let mut rng = StdRng::seed_from_u64(2);
+
+        // U-shape, whose notch must never be sampled
+        let vertices = vec![
+            Vector2::new(-2., 2.),
+            Vector2::new(-1., 2.),
+            Vector2::new(-1., -1.),
+            Vector2::new(1., -1.),
+            Vector2::new(1., 2.),
+            Vector2::new(2., 2.),
+            Vector2::new(2., -2.),
+            Vector2::new(-2., -2.),
+        ];
+
+        for _ in 0..SAMPLES {
+            let p = sample_polygon(&vertices, &mut rng).unwrap();
+
+            assert!(contains(&vertices, p), "{p} outside of the polygon");
+            assert!(
+                !(p.x > -1. && p.x < 1. && p.y > -1.),
+                "{p} within the notch"
+            );
+        }
+    }
+
+    #[test]
+    fn degenerate_polygons_fall_back_to_the_origin() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let origin = Vector2::new(5., 5.);
+        let line = SpawnRegion::Polygon(vec![
+            Vector2::new(0., 0.),
+            Vector2::new(1., 0.),
+            Vector2::new(2., 0.),
+        ]);
+
+        assert!(distance(line.sample(origin, &mut rng), origin) < f32::EPSILON);
+    }
+
+    #[test]
+    fn same_seed_samples_the_same_locations() {
+        let region = SpawnRegion::Segment {
+            from: Vector2::new(-1., 0.),
+            to: Vector2::new(1., 3.),
+        };
+        let sample = |seed| {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let mut sampler = RegionSampler::new(SpawnSampling::PoissonDisk { min_distance: 0.5 });
+
+            (0..10)
+                .map(|_| sampler.sample(&region, Vector2::new(0., 0.), &mut rng))
+                .map(|p| (p.x, p.y))
+                .collect::<Vec<(f32, f32)>>()
+        };
+
+        assert_eq!(sample(7), sample(7));
+    }
+}
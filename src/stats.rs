@@ -0,0 +1,175 @@
+//! Rolling timing statistics of the simulation loop.<br>
+//! All durations are recorded in seconds and shown in milliseconds.
+
+use std::collections::VecDeque;
+
+/// Number of samples the statistics are computed over.
+pub const STATS_WINDOW: usize = 600;
+
+/// Statistics over all samples of a `RollingStats` window.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Summary {
+    pub mean: f32,
+    pub median: f32,
+    pub p95: f32,
+    pub p99: f32,
+    pub max: f32,
+}
+
+/// The last `window` samples of a single measurement.
+pub struct RollingStats {
+    pub samples: VecDeque<f32>,
+    pub window: usize,
+}
+
+impl RollingStats {
+    pub fn new(window: usize) -> RollingStats {
+        RollingStats {
+            samples: VecDeque::with_capacity(window),
+            window,
+        }
+    }
+
+    pub fn push(&mut self, sample: f32) {
+        if self.samples.len() == self.window {
+            self.samples.pop_front();
+        }
+
+        self.samples.push_back(sample);
+    }
+
+    pub fn last(&self) -> Option<f32> {
+        self.samples.back().copied()
+    }
+
+    pub fn clear(&mut self) {
+        self.samples.clear();
+    }
+
+    pub fn summary(&self) -> Option<Summary> {
+        if self.samples.is_empty() {
+            return None;
+        }
+
+        let mut sorted: Vec<f32> = self.samples.iter().copied().collect();
+        sorted.sort_by(f32::total_cmp);
+
+        Some(Summary {
+            mean: sorted.iter().sum::<f32>() / sorted.len() as f32,
+            median: percentile(&sorted, 50.),
+            p95: percentile(&sorted, 95.),
+            p99: percentile(&sorted, 99.),
+            max: sorted[sorted.len() - 1],
+        })
+    }
+}
+
+/// Nearest-rank percentile of already sorted samples.
+fn percentile(sorted: &[f32], p: f32) -> f32 {
+    let rank = (p / 100. * sorted.len() as f32).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// Durations of world updates, rendering and whole frames.
+pub struct TimingStats {
+    pub physics: RollingStats,
+    pub render: RollingStats,
+    pub frame: RollingStats,
+}
+
+impl TimingStats {
+    pub fn new(window: usize) -> TimingStats {
+        TimingStats {
+            physics: RollingStats::new(window),
+            render: RollingStats::new(window),
+            frame: RollingStats::new(window),
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.physics.clear();
+        self.render.clear();
+        self.frame.clear();
+    }
+
+    pub fn all(&self) -> [(&'static str, &RollingStats); 3] {
+        [
+            ("physics", &self.physics),
+            ("render", &self.render),
+            ("frame", &self.frame),
+        ]
+    }
+}
+
+impl Default for TimingStats {
+    fn default() -> Self {
+        Self::new(STATS_WINDOW)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filled(window: usize, samples: impl IntoIterator<Item = f32>) -> RollingStats {
+        let mut stats = RollingStats::new(window);
+        samples.into_iter().for_each(|sample| stats.push(sample));
+        stats
+    }
+
+    #[test]
+    fn percentiles_use_the_nearest_rank() {
+        // pushed in reverse, so the samples have to be sorted first
+        let stats = filled(100, (1..=100).rev().map(|n| n as f32));
+
+        assert_eq!(
+            stats.summary(),
+            Some(Summary {
+                mean: 50.5,
+                median: 50.,
+                p95: 95.,
+                p99: 99.,
+                max: 100.,
+            })
+        );
+    }
+
+    #[test]
+    fn percentiles_of_small_windows_round_up() {
+        let summary = filled(10, (1..=10).map(|n| n as f32)).summary().unwrap();
+
+        assert_eq!(summary.median, 5.);
+        assert_eq!(summary.p95, 10.);
+        assert_eq!(summary.p99, 10.);
+    }
+
+    #[test]
+    fn single_sample_is_every_percentile() {
+        let summary = filled(10, [2.]).summary().unwrap();
+
+        assert_eq!(summary.median, 2.);
+        assert_eq!(summary.p99, 2.);
+        assert_eq!(summary.max, 2.);
+    }
+
+    #[test]
+    fn window_drops_the_oldest_samples() {
+        let stats = filled(10, (1..=20).map(|n| n as f32));
+        let summary = stats.summary().unwrap();
+
+        assert_eq!(stats.samples.len(), 10);
+        assert_eq!(stats.last(), Some(20.));
+        assert_eq!(summary.mean, 15.5);
+        assert_eq!(summary.max, 20.);
+    }
+
+    #[test]
+    fn empty_and_cleared_stats_have_no_summary() {
+        let mut stats = filled(10, [1., 2.]);
+        assert!(stats.summary().is_some());
+
+        stats.clear();
+        assert_eq!(stats.summary(), None);
+        assert_eq!(RollingStats::new(10).summary(), None);
+    }
+}
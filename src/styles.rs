@@ -0,0 +1,160 @@
+//! A registry of render styles, that scenes can use to customize the appearance of their bodies.<br>
+//! Styles are keyed by body name, with the material name acting as a fallback tag.<br>
+//! Textures referenced by styles are loaded from local image files via `load_textures`.
+
+use std::collections::HashMap;
+
+use macroquad::{
+    color::Color,
+    texture::{load_texture, Texture2D},
+};
+use rustycs::entities::{attractor::Attractor, body::Body};
+
+const DEFAULT_LINE_THICKNESS: f32 = 2.;
+
+/// How bodies without a registered style are rendered.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FillMode {
+    Filled,
+    Outline,
+    Both,
+}
+
+impl Default for FillMode {
+    fn default() -> Self {
+        Self::Both
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct BodyStyle {
+    pub fill_color: Option<Color>,
+    pub outline_color: Option<Color>,
+    pub line_thickness: f32,
+    pub show_label: bool,
+    pub texture_path: Option<&'static str>,
+}
+
+impl BodyStyle {
+    pub fn filled(color: Color) -> BodyStyle {
+        BodyStyle {
+            fill_color: Some(color),
+            outline_color: None,
+            line_thickness: DEFAULT_LINE_THICKNESS,
+            show_label: false,
+            texture_path: None,
+        }
+    }
+
+    pub fn outlined(color: Color) -> BodyStyle {
+        BodyStyle {
+            fill_color: None,
+            outline_color: Some(color),
+            line_thickness: DEFAULT_LINE_THICKNESS,
+            show_label: false,
+            texture_path: None,
+        }
+    }
+
+    /// Sprite style, bodies fall back to their default style in wireframe mode.
+    pub fn textured(texture_path: &'static str) -> BodyStyle {
+        BodyStyle {
+            fill_color: None,
+            outline_color: None,
+            line_thickness: DEFAULT_LINE_THICKNESS,
+            show_label: false,
+            texture_path: Some(texture_path),
+        }
+    }
+
+    pub fn with_outline(mut self, color: Color, line_thickness: f32) -> BodyStyle {
+        self.outline_color = Some(color);
+        self.line_thickness = line_thickness;
+        self
+    }
+
+    pub fn labelled(mut self) -> BodyStyle {
+        self.show_label = true;
+        self
+    }
+
+    pub fn with_texture(mut self, texture_path: &'static str) -> BodyStyle {
+        self.texture_path = Some(texture_path);
+        self
+    }
+
+    pub fn has_wireframe(&self) -> bool {
+        self.fill_color.is_some() || self.outline_color.is_some()
+    }
+}
+
+#[derive(Default)]
+pub struct StyleRegistry {
+    by_name: HashMap<&'static str, BodyStyle>,
+    by_tag: HashMap<&'static str, BodyStyle>,
+    textures: HashMap<&'static str, Texture2D>,
+    fill_mode: FillMode,
+}
+
+impl StyleRegistry {
+    pub fn new() -> StyleRegistry {
+        Self::default()
+    }
+
+    pub fn with_fill_mode(mut self, fill_mode: FillMode) -> StyleRegistry {
+        self.fill_mode = fill_mode;
+        self
+    }
+
+    pub fn fill_mode(&self) -> FillMode {
+        self.fill_mode
+    }
+
+    /// Style applied to all bodies and attractors with the given name.
+    pub fn insert_name(&mut self, name: &'static str, style: BodyStyle) {
+        self.by_name.insert(name, style);
+    }
+
+    /// Style applied to all unnamed (or unregistered) bodies made of the given material.
+    pub fn insert_tag(&mut self, material_name: &'static str, style: BodyStyle) {
+        self.by_tag.insert(material_name, style);
+    }
+
+    pub fn style_of(&self, body: &Body) -> Option<&BodyStyle> {
+        body.name
+            .and_then(|name| self.by_name.get(name))
+            .or_else(|| self.by_tag.get(body.material.name))
+    }
+
+    pub fn texture_of(&self, style: &BodyStyle) -> Option<&Texture2D> {
+        style.texture_path.and_then(|path| self.textures.get(path))
+    }
+
+    /// Loads all textures referenced by registered styles.<br>
+    /// Textures that fail to load are reported and their bodies are rendered without sprite.
+    pub async fn load_textures(&mut self) {
+        let paths: Vec<&'static str> = self
+            .by_name
+            .values()
+            .chain(self.by_tag.values())
+            .filter_map(|style| style.texture_path)
+            .collect();
+
+        for path in paths {
+            if self.textures.contains_key(path) {
+                continue;
+            }
+
+            match load_texture(path).await {
+                Ok(texture) => {
+                    self.textures.insert(path, texture);
+                }
+                Err(err) => eprintln!("could not load texture '{path}': {err}"),
+            }
+        }
+    }
+
+    pub fn style_of_attractor(&self, attractor: &Attractor) -> Option<&BodyStyle> {
+        attractor.name.and_then(|name| self.by_name.get(name))
+    }
+}
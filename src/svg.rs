@@ -0,0 +1,205 @@
+//! A drawing backend that writes everything the renderer draws into an SVG document,
+//! so the current view can be exported as a figure, using the same styling rules as on screen.
+
+use std::{fmt::Write as _, fs, io, path::Path};
+
+use macroquad::{color::Color, math::Vec2, texture::Texture2D};
+
+use crate::backend::DrawBackend;
+
+/// Coordinate system of the exported SVG.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SvgSpace {
+    /// screen pixels, as rendered
+    Screen,
+    /// world meters, `origin` being the screen position of the world origin (camera offset included)
+    World { ptm_ratio: f32, origin: (f32, f32) },
+}
+
+pub struct SvgBackend {
+    screen: (f32, f32),
+    mouse: (f32, f32),
+    space: SvgSpace,
+    elements: String,
+}
+
+impl SvgBackend {
+    pub fn new(width: f32, height: f32, space: SvgSpace) -> SvgBackend {
+        SvgBackend {
+            screen: (width, height),
+            // no hover information in exports
+            mouse: (f32::NAN, f32::NAN),
+            space,
+            elements: String::new(),
+        }
+    }
+
+    pub fn to_svg(&self) -> String {
+        let (w, h) = self.screen;
+
+        let (view_box, group) = match self.space {
+            SvgSpace::Screen => (format!("0 0 {w} {h}"), ""),
+            SvgSpace::World { ptm_ratio, origin } => (
+                format!(
+                    "{} {} {} {}",
+                    -origin.0 / ptm_ratio,
+                    -origin.1 / ptm_ratio,
+                    w / ptm_ratio,
+                    h / ptm_ratio
+                ),
+                // world coordinates have an inverted y-axis
+                " transform=\"scale(1 -1)\"",
+            ),
+        };
+
+        format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"{view_box}\">\n<g{group}>\n{}</g>\n</svg>\n",
+            self.elements
+        )
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.to_svg())
+    }
+}
+
+impl SvgBackend {
+    fn point(&self, x: f32, y: f32) -> (f32, f32) {
+        match self.space {
+            SvgSpace::Screen => (x, y),
+            SvgSpace::World { ptm_ratio, origin } => {
+                ((x - origin.0) / ptm_ratio, -(y - origin.1) / ptm_ratio)
+            }
+        }
+    }
+
+    fn length(&self, l: f32) -> f32 {
+        match self.space {
+            SvgSpace::Screen => l,
+            SvgSpace::World { ptm_ratio, .. } => l / ptm_ratio,
+        }
+    }
+
+    fn polygon(&mut self, points: &[(f32, f32)], color: Color) {
+        let points: Vec<String> = points
+            .iter()
+            .map(|(x, y)| {
+                let (x, y) = self.point(*x, *y);
+                format!("{x},{y}")
+            })
+            .collect();
+
+        let _ = writeln!(
+            self.elements,
+            "<polygon points=\"{}\" {}/>",
+            points.join(" "),
+            fill(color)
+        );
+    }
+}
+
+impl DrawBackend for SvgBackend {
+    fn screen_size(&self) -> (f32, f32) {
+        self.screen
+    }
+
+    fn mouse_position(&self) -> (f32, f32) {
+        self.mouse
+    }
+
+    fn clear(&mut self, color: Color) {
+        let (w, h) = self.screen;
+        self.polygon(&[(0., 0.), (w, 0.), (w, h), (0., h)], color);
+    }
+
+    fn line(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, thickness: f32, color: Color) {
+        let (x1, y1) = self.point(x1, y1);
+        let (x2, y2) = self.point(x2, y2);
+        let thickness = self.length(thickness);
+
+        let _ = writeln!(
+            self.elements,
+            "<line x1=\"{x1}\" y1=\"{y1}\" x2=\"{x2}\" y2=\"{y2}\" stroke-width=\"{thickness}\" {}/>",
+            stroke(color)
+        );
+    }
+
+    fn circle(&mut self, x: f32, y: f32, r: f32, color: Color) {
+        let (x, y) = self.point(x, y);
+        let r = self.length(r);
+
+        let _ = writeln!(
+            self.elements,
+            "<circle cx=\"{x}\" cy=\"{y}\" r=\"{r}\" {}/>",
+            fill(color)
+        );
+    }
+
+    fn circle_lines(&mut self, x: f32, y: f32, r: f32, thickness: f32, color: Color) {
+        let (x, y) = self.point(x, y);
+        let (r, thickness) = (self.length(r), self.length(thickness));
+
+        let _ = writeln!(
+            self.elements,
+            "<circle cx=\"{x}\" cy=\"{y}\" r=\"{r}\" fill=\"none\" stroke-width=\"{thickness}\" {}/>",
+            stroke(color)
+        );
+    }
+
+    fn triangle(&mut self, a: Vec2, b: Vec2, c: Vec2, color: Color) {
+        self.polygon(&[(a.x, a.y), (b.x, b.y), (c.x, c.y)], color);
+    }
+
+    fn text(&mut self, text: &str, x: f32, y: f32, font_size: f32, color: Color) {
+        let (x, y) = self.point(x, y);
+        let font_size = self.length(font_size);
+
+        let placement = match self.space {
+            SvgSpace::Screen => format!("x=\"{x}\" y=\"{y}\""),
+            // undo the inverted y-axis of the world group, so text stays readable
+            SvgSpace::World { .. } => format!("transform=\"translate({x} {y}) scale(1 -1)\""),
+        };
+
+        let _ = writeln!(
+            self.elements,
+            "<text {placement} font-family=\"monospace\" font-size=\"{font_size}\" {}>{}</text>",
+            fill(color),
+            escape(text)
+        );
+    }
+
+    /// Textures are not embedded, the textured area is exported as a gray placeholder.
+    fn textured_polygon(
+        &mut self,
+        _texture: &Texture2D,
+        points: &[Vec2],
+        _uvs: &[Vec2],
+        _triangles: &[[usize; 3]],
+    ) {
+        let points: Vec<(f32, f32)> = points.iter().map(|p| (p.x, p.y)).collect();
+        self.polygon(&points, Color::new(0.5, 0.5, 0.5, 0.5));
+    }
+}
+
+fn rgb(color: Color) -> String {
+    format!(
+        "rgb({},{},{})",
+        (color.r * 255.).round(),
+        (color.g * 255.).round(),
+        (color.b * 255.).round()
+    )
+}
+
+fn fill(color: Color) -> String {
+    format!("fill=\"{}\" fill-opacity=\"{}\"", rgb(color), color.a)
+}
+
+fn stroke(color: Color) -> String {
+    format!("stroke=\"{}\" stroke-opacity=\"{}\"", rgb(color), color.a)
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
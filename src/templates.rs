@@ -0,0 +1,215 @@
+//! Body templates, that spawners draw from at random according to their weights.<br>
+//! A template describes a shape, a size range and a material, the actual body is generated on demand.
+
+use rand::Rng;
+use rustycs::{
+    entities::{
+        body::Body,
+        material::{self, Material},
+    },
+    math::Vector2,
+};
+
+use crate::{
+    polygon_validation::{checked_polygon, PolygonError},
+    rustycs_utility as util,
+};
+
+/// Presets of templates, that spawners placed in the editor can choose from.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SpawnerPreset {
+    Circles,
+    Boxes,
+    Polygons,
+    /// shapes of the polygon generators in `rustycs_utility`
+    Generated,
+    Random,
+}
+
+impl Default for SpawnerPreset {
+    fn default() -> Self {
+        Self::Circles
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum TemplateShape {
+    /// size is the radius
+    Circle,
+    /// size is sampled separately for width and height
+    Box,
+    /// polygon constructor in clockwise order, size is its scale (e.g. `poly_simple`)
+    Polygon(fn(f32) -> Vec<Vector2>),
+    /// regular polygon with the given number of vertices, size is the radius
+    Regular(usize),
+    /// size is sampled separately for width and height, corners are rounded by a quarter of the shorter side
+    RoundedBox,
+    /// horizontal capsule, size is the total length and a quarter of it the radius
+    Capsule,
+    /// convex hull of the given number of random points, size is the radius
+    RandomConvex(usize),
+}
+
+#[derive(Clone, Debug)]
+pub struct BodyTemplate {
+    pub shape: TemplateShape,
+    pub min_size: f32,
+    pub max_size: f32,
+    pub material: Material,
+    /// relative probability of this template being chosen
+    pub weight: f32,
+}
+
+impl BodyTemplate {
+    pub fn new(
+        shape: TemplateShape,
+        min_size: f32,
+        max_size: f32,
+        material: Material,
+        weight: f32,
+    ) -> BodyTemplate {
+        BodyTemplate {
+            shape,
+            min_size,
+            max_size,
+            material,
+            weight,
+        }
+    }
+
+    /// Fails with the reason, if a polygon template produces an invalid polygon.
+    pub fn instantiate(&self, location: Vector2, rng: &mut impl Rng) -> Result<Body, PolygonError> {
+        let mut size = || {
+            if self.max_size > self.min_size {
+                rng.gen_range(self.min_size..=self.max_size)
+            } else {
+                self.min_size
+            }
+        };
+
+        let (x, y, material) = (location.x, location.y, self.material.clone());
+
+        match self.shape {
+            TemplateShape::Circle => Ok(Body::circle(x, y, size(), material)),
+            TemplateShape::Box => {
+                let (width, height) = (size(), size());
+                Ok(Body::obb(x, y, width, height, material))
+            }
+            TemplateShape::Polygon(vertices) => checked_polygon(x, y, vertices(size()), material),
+            TemplateShape::Regular(n) => {
+                checked_polygon(x, y, util::poly_regular(n, size()), material)
+            }
+            TemplateShape::RoundedBox => {
+                let (width, height) = (size(), size());
+                let vertices = util::poly_rounded_rect(width, height, 0.25 * width.min(height), 4);
+                checked_polygon(x, y, vertices, material)
+            }
+            TemplateShape::Capsule => {
+                let length = size();
+                let vertices = util::poly_capsule(0.5 * length, 0.25 * length, 6);
+                checked_polygon(x, y, vertices, material)
+            }
+            TemplateShape::RandomConvex(n) => {
+                let radius = size();
+                checked_polygon(x, y, util::poly_random_convex(n, radius, rng), material)
+            }
+        }
+    }
+}
+
+/// Index of a template picked with a probability proportional to its weight.
+pub fn pick_weighted(templates: &[BodyTemplate], rng: &mut impl Rng) -> Option<usize> {
+    let total: f32 = templates.iter().map(|t| t.weight.max(0.)).sum();
+
+    if total <= 0. {
+        return None;
+    }
+
+    let mut pick = rng.gen_range(0.0..total);
+
+    templates
+        .iter()
+        .position(|t| {
+            pick -= t.weight.max(0.);
+            pick < 0.
+        })
+        // rounding can leave a tiny remainder, that belongs to the last template with a weight
+        .or_else(|| templates.iter().rposition(|t| t.weight > 0.))
+}
+
+impl SpawnerPreset {
+    pub fn next(self) -> SpawnerPreset {
+        use SpawnerPreset::*;
+
+        match self {
+            Circles => Boxes,
+            Boxes => Polygons,
+            Polygons => Generated,
+            Generated => Random,
+            Random => Circles,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        use SpawnerPreset::*;
+
+        match self {
+            Circles => "circles",
+            Boxes => "boxes",
+            Polygons => "polygons",
+            Generated => "generated",
+            Random => "random",
+        }
+    }
+
+    pub fn templates(self) -> Vec<BodyTemplate> {
+        use SpawnerPreset::*;
+
+        match self {
+            Circles => vec![BodyTemplate::new(
+                TemplateShape::Circle,
+                0.1,
+                0.25,
+                material::RUBBER,
+                1.,
+            )],
+            Boxes => vec![BodyTemplate::new(
+                TemplateShape::Box,
+                0.3,
+                0.6,
+                material::DEFAULT,
+                1.,
+            )],
+            Polygons => vec![
+                BodyTemplate::new(
+                    TemplateShape::Polygon(util::poly_simple),
+                    1.,
+                    2.,
+                    material::STONE,
+                    1.,
+                ),
+                BodyTemplate::new(
+                    TemplateShape::Polygon(util::poly_triangle),
+                    1.,
+                    2.,
+                    material::STONE,
+                    1.,
+                ),
+            ],
+            Generated => vec![
+                BodyTemplate::new(TemplateShape::Regular(5), 0.2, 0.4, material::PLASTIC, 1.),
+                BodyTemplate::new(TemplateShape::Regular(6), 0.2, 0.4, material::PLASTIC, 1.),
+                BodyTemplate::new(TemplateShape::RoundedBox, 0.3, 0.6, material::DEFAULT, 1.),
+                BodyTemplate::new(TemplateShape::Capsule, 0.4, 0.8, material::RUBBER, 1.),
+                BodyTemplate::new(
+                    TemplateShape::RandomConvex(8),
+                    0.2,
+                    0.4,
+                    material::STONE,
+                    1.,
+                ),
+            ],
+            Random => util::random_body_templates(),
+        }
+    }
+}